@@ -0,0 +1,9 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=ffi_harness.c");
+    println!("cargo:rerun-if-changed=../include/one_err.h");
+    cc::Build::new()
+        .file("ffi_harness.c")
+        .include("../include")
+        .compile("one_err_ffi_harness");
+}
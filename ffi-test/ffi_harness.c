@@ -0,0 +1,71 @@
+/*
+ * Exercises include/one_err.h from C. Compiled by the build.rs of the
+ * unpublished ffi-test crate, and run by its `c_harness` test.
+ * Returns 0 on success, or the line number of the first failed check.
+ */
+
+#include <errno.h>
+#include <string.h>
+
+#include "one_err.h"
+
+#define CHECK(x) \
+    do { \
+        if (!(x)) return __LINE__; \
+    } while (0)
+
+int one_err_ffi_harness(void) {
+    one_err_t *e = one_err_new("NotFound", "no such thing");
+    CHECK(e != NULL);
+    CHECK(strcmp(one_err_kind(e), "NotFound") == 0);
+    CHECK(strcmp(one_err_message(e), "no such thing") == 0);
+    CHECK(one_err_errno(e) == ENOENT);
+
+    errno = 0;
+    CHECK(one_err_set_errno(e) == ENOENT);
+    CHECK(errno == ENOENT);
+
+    char *json = one_err_to_json(e);
+    CHECK(json != NULL);
+    one_err_free(e);
+
+    e = one_err_from_json(json);
+    one_err_string_free(json);
+    CHECK(e != NULL);
+    CHECK(strcmp(one_err_kind(e), "NotFound") == 0);
+    one_err_free(e);
+
+    e = one_err_from_json(
+        "{\"error\":\"MyKind\",\"path\":\"/tmp\",\"n\":-3,"
+        "\"u\":7,\"f\":1.5,\"b\":true}");
+    CHECK(e != NULL);
+    CHECK(strcmp(one_err_kind(e), "MyKind") == 0);
+    CHECK(one_err_message(e) == NULL);
+    CHECK(one_err_errno(e) == -1);
+    errno = EINTR;
+    CHECK(one_err_set_errno(e) == 0);
+    CHECK(errno == EINTR);
+
+    char *path = one_err_get_field_str(e, "path");
+    CHECK(path != NULL && strcmp(path, "/tmp") == 0);
+    one_err_string_free(path);
+    CHECK(one_err_get_field_str(e, "missing") == NULL);
+
+    int64_t n = 0;
+    CHECK(one_err_get_field_i64(e, "n", &n) == 1 && n == -3);
+    uint64_t u = 0;
+    CHECK(one_err_get_field_u64(e, "u", &u) == 1 && u == 7);
+    double f = 0;
+    CHECK(one_err_get_field_f64(e, "f", &f) == 1 && f == 1.5);
+    bool b = false;
+    CHECK(one_err_get_field_bool(e, "b", &b) == 1 && b);
+    CHECK(one_err_get_field_bool(e, "path", &b) == 0);
+    one_err_free(e);
+
+    CHECK(one_err_from_json("not json") == NULL);
+    CHECK(one_err_new(NULL, NULL) == NULL);
+    CHECK(one_err_kind(NULL) == NULL);
+    one_err_free(NULL);
+
+    return 0;
+}
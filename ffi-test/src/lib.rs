@@ -0,0 +1,16 @@
+//! Tests for the one_err `ffi` module that need a C toolchain,
+//! kept out of the published crate.
+//!
+//! - `c_harness` runs `ffi_harness.c`, compiled against
+//!   `include/one_err.h` by this crate's build.rs.
+//! - `header_matches_ffi` checks that every declaration in
+//!   `include/one_err.h` matches an `extern "C"` fn in `src/ffi.rs`,
+//!   and that no fn is missing from the header.
+
+#![deny(warnings, missing_docs)]
+
+// make sure the one_err ffi symbols get linked
+use one_err as _;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::os::raw::c_int;
+
+extern "C" {
+    fn one_err_ffi_harness() -> c_int;
+}
+
+#[test]
+fn c_harness() {
+    // safety: the harness only calls the one_err ffi functions
+    let res = unsafe { one_err_ffi_harness() };
+    assert_eq!(0, res, "check failed at ffi-test/ffi_harness.c:{}", res);
+}
+
+/// fn name -> (param types, return type), as rust types
+type Sigs = BTreeMap<String, (Vec<String>, String)>;
+
+#[test]
+fn header_matches_ffi() {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+    let header =
+        std::fs::read_to_string(format!("{}/include/one_err.h", root)).unwrap();
+    let ffi = std::fs::read_to_string(format!("{}/src/ffi.rs", root))
+        .unwrap()
+        .replace("\r\n", "\n");
+
+    let c = header_sigs(&header);
+    let rust = rust_sigs(&ffi);
+    assert!(!rust.is_empty());
+    for name in rust.keys() {
+        assert!(c.contains_key(name), "{} missing from one_err.h", name);
+    }
+    for (name, sig) in c.iter() {
+        assert_eq!(rust.get(name), Some(sig), "one_err.h declares {}", name);
+    }
+}
+
+fn header_sigs(header: &str) -> Sigs {
+    let mut text = String::new();
+    let mut rest = header;
+    while let Some(start) = rest.find("/*") {
+        text.push_str(&rest[..start]);
+        let end = rest[start..].find("*/").expect("unterminated comment");
+        rest = &rest[start + end + 2..];
+    }
+    text.push_str(rest);
+
+    let text = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#') && *l != "extern \"C\" {" && *l != "}")
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut out = Sigs::new();
+    for decl in text.split(';').map(str::trim) {
+        if decl.is_empty() || decl.starts_with("typedef") {
+            continue;
+        }
+        let open = decl.find('(').expect("a function declaration");
+        let close = decl.rfind(')').expect("a function declaration");
+        let (ret, name) = split_ident(&decl[..open]);
+        let params = match decl[open + 1..close].trim() {
+            "void" => Vec::new(),
+            params => params
+                .split(',')
+                .map(|p| c_to_rust(split_ident(p).0))
+                .collect(),
+        };
+        out.insert(name.to_string(), (params, c_to_rust(ret)));
+    }
+    out
+}
+
+/// split a C declaration into its type and trailing identifier
+fn split_ident(decl: &str) -> (&str, &str) {
+    let decl = decl.trim();
+    let at = decl
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    (&decl[..at], &decl[at..])
+}
+
+fn c_to_rust(ty: &str) -> String {
+    let ty = ty.replace('*', " * ");
+    let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+    match ty.as_str() {
+        "void" => "()",
+        "int" => "c_int",
+        "const char *" => "*const c_char",
+        "char *" => "*mut c_char",
+        "const one_err_t *" => "*const OneErrHandle",
+        "one_err_t *" => "*mut OneErrHandle",
+        "bool *" => "*mut bool",
+        "int64_t *" => "*mut i64",
+        "uint64_t *" => "*mut u64",
+        "double *" => "*mut f64",
+        _ => panic!("unmapped C type '{}' in one_err.h", ty),
+    }
+    .to_string()
+}
+
+fn rust_sigs(ffi: &str) -> Sigs {
+    // the typed getters are generated by the get_field! macro
+    let mut getters = Vec::new();
+    let invocation = ffi.find("\nget_field! {").expect("get_field! call");
+    for line in ffi[invocation..].lines().skip(1) {
+        let line = line.trim();
+        if line == "}" {
+            break;
+        }
+        if let Some((name, ty)) = line.trim_end_matches(',').split_once(": ") {
+            getters.push((name.to_string(), ty.to_string()));
+        }
+    }
+    assert!(!getters.is_empty());
+
+    let mut out = Sigs::new();
+    for item in ffi.split("extern \"C\" fn ").skip(1) {
+        let open = item.find('(').unwrap();
+        let close = item.find(')').unwrap();
+        let body = item.find('{').unwrap();
+        let name = item[..open].trim();
+        let params = item[open + 1..close]
+            .split(',')
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| p.split_once(':').unwrap().1.trim().to_string())
+            .collect::<Vec<_>>();
+        let ret = match item[close + 1..body].split_once("->") {
+            Some((_, ret)) => ret.trim().to_string(),
+            None => "()".to_string(),
+        };
+        if name == "$name" {
+            for (name, ty) in getters.iter() {
+                let params = params.iter().map(|p| p.replace("$t", ty));
+                out.insert(name.clone(), (params.collect(), ret.clone()));
+            }
+        } else {
+            out.insert(name.to_string(), (params, ret));
+        }
+    }
+    out
+}
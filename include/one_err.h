@@ -0,0 +1,65 @@
+/*
+ * C bindings for the one_err crate, see the `ffi` module docs.
+ * Requires the `ffi` feature.
+ *
+ * A one_err_t* is an owned handle, release it with one_err_free().
+ * Strings from one_err_kind() and one_err_message() are borrowed from the
+ * handle. Strings from one_err_get_field_str() and one_err_to_json() are
+ * owned by the caller, release them with one_err_string_free().
+ * All functions accept NULL handles.
+ */
+
+#ifndef ONE_ERR_H
+#define ONE_ERR_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct OneErrHandle one_err_t;
+
+/* create an error, message may be NULL. NULL if kind is NULL or not utf8 */
+one_err_t *one_err_new(const char *kind, const char *message);
+
+/* release an error handle */
+void one_err_free(one_err_t *e);
+
+/* release a string returned by one_err_get_field_str / one_err_to_json */
+void one_err_string_free(char *s);
+
+/* the str kind, e.g. "NotFound", "ENOENT" or a custom kind */
+const char *one_err_kind(const one_err_t *e);
+
+/* the errno on the current platform, or -1 */
+int one_err_errno(const one_err_t *e);
+
+/* set the C errno from the error, returns the value that was set.
+ * errors without an errno leave errno unchanged and return 0 */
+int one_err_set_errno(const one_err_t *e);
+
+/* the message, or NULL */
+const char *one_err_message(const one_err_t *e);
+
+/* a string field, or NULL. free with one_err_string_free */
+char *one_err_get_field_str(const one_err_t *e, const char *name);
+
+/* typed fields, return 1 and write to out if the field exists, else 0 */
+int one_err_get_field_bool(const one_err_t *e, const char *name, bool *out);
+int one_err_get_field_i64(const one_err_t *e, const char *name, int64_t *out);
+int one_err_get_field_u64(const one_err_t *e, const char *name, uint64_t *out);
+int one_err_get_field_f64(const one_err_t *e, const char *name, double *out);
+
+/* encode as json, free with one_err_string_free */
+char *one_err_to_json(const one_err_t *e);
+
+/* decode from json, NULL if the json is invalid */
+one_err_t *one_err_from_json(const char *json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ONE_ERR_H */
@@ -0,0 +1,451 @@
+//! Compact, self-describing binary encoding of OneErr, for high-volume
+//! error streams.
+//!
+//! A stream is a version byte followed by any number of records.
+//! All integers are LEB128 varints. A record is:
+//!
+//! - the kind: a non-zero tag for well-known io kinds and errnos,
+//!   or `0` followed by the length-prefixed utf8 kind string
+//! - the field count
+//! - for each field, the name then the value
+//!
+//! A field name is either `len << 1` followed by `len` utf8 bytes,
+//! which also appends the name to the stream's string table, or
+//! `index << 1 | 1`, referring to a name already in the table. The table
+//! holds at most [MAX_NAMES] names, later names are always sent in full.
+//! Records are therefore only decodable in stream order.
+//!
+//! A value is a type byte, followed by nothing for null and bools,
+//! a zigzag varint for i64, a varint for u64, 8 little-endian bytes for
+//! f64, or a length-prefixed utf8 string. Non-finite floats are encoded
+//! as null, as in the json form, so that decoding reproduces the json
+//! form of the original exactly.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::compact::*;
+//!
+//! let mut stream = Encoder::new(Vec::new());
+//! for path in ["/a", "/b"] {
+//!     let mut err = OneErr::from(ErrNo::NoEnt);
+//!     err.set_field("path", path);
+//!     stream.encode(&err).unwrap();
+//! }
+//!
+//! let data = stream.into_inner();
+//! let mut paths = Vec::new();
+//! for err in Decoder::new(&data[..]) {
+//!     let err = err.unwrap();
+//!     paths.push(err.get_field::<_, &str>("path").unwrap().to_string());
+//! }
+//! assert_eq!(vec!["/a", "/b"], paths);
+//! ```
+
+use crate::parse_error::*;
+use crate::*;
+use std::io::{Read, Write};
+
+/// The format version byte that starts each stream.
+pub const VERSION: u8 = 1;
+
+/// The maximum number of field names interned per stream.
+pub const MAX_NAMES: usize = 4096;
+
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+const I64: u8 = 3;
+const U64: u8 = 4;
+const F64: u8 = 5;
+const STRING: u8 = 6;
+
+/// Stream encoder, writing records to an underlying writer.
+/// Each record is written with a single `write_all` call.
+pub struct Encoder<W: Write> {
+    writer: W,
+    names: std::collections::HashMap<Box<str>, u64>,
+    started: bool,
+}
+
+impl<W: Write> std::fmt::Debug for Encoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encoder")
+            .field("names", &self.names.len())
+            .finish()
+    }
+}
+
+impl<W: Write> Encoder<W> {
+    /// Begin a new stream on `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            names: std::collections::HashMap::new(),
+            started: false,
+        }
+    }
+
+    /// Encode one record onto the stream.
+    pub fn encode(&mut self, err: &OneErr) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        if !self.started {
+            buf.push(VERSION);
+        }
+
+        let kind = err.str_kind();
+        match kind_to_tag(kind) {
+            Some(tag) => put_varint(&mut buf, tag),
+            None => {
+                put_varint(&mut buf, 0);
+                put_str(&mut buf, kind);
+            }
+        }
+
+        let fields = match &*err.priv_as_inner().0 {
+            Some(f) => f
+                .iter()
+                .filter(|(n, _)| !matches!(&***n, ERROR | OS))
+                .collect(),
+            None => Vec::new(),
+        };
+        // names are only interned once the record is written,
+        // so a failed write does not desync the string table
+        let mut new_names = Vec::new();
+        put_varint(&mut buf, fields.len() as u64);
+        for (name, value) in fields {
+            self.put_name(&mut buf, &mut new_names, name);
+            put_value(&mut buf, value);
+        }
+
+        self.writer.write_all(&buf)?;
+        self.started = true;
+        for name in new_names {
+            let index = self.names.len() as u64;
+            self.names.insert(name.into(), index);
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn put_name<'a>(
+        &self,
+        buf: &mut Vec<u8>,
+        new_names: &mut Vec<&'a str>,
+        name: &'a str,
+    ) {
+        if let Some(index) = self.names.get(name) {
+            put_varint(buf, index << 1 | 1);
+            return;
+        }
+        put_varint(buf, (name.len() as u64) << 1);
+        buf.extend_from_slice(name.as_bytes());
+        if self.names.len() + new_names.len() < MAX_NAMES {
+            new_names.push(name);
+        }
+    }
+}
+
+fn put_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    put_varint(buf, s.len() as u64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn put_value(buf: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => buf.push(NULL),
+        Value::Bool(false) => buf.push(FALSE),
+        Value::Bool(true) => buf.push(TRUE),
+        Value::I64(i) => {
+            buf.push(I64);
+            put_varint(buf, ((i << 1) ^ (i >> 63)) as u64);
+        }
+        Value::U64(u) => {
+            buf.push(U64);
+            put_varint(buf, *u);
+        }
+        Value::F64(f) if f.is_finite() => {
+            buf.push(F64);
+            buf.extend_from_slice(&f.to_le_bytes());
+        }
+        Value::F64(_) => buf.push(NULL),
+        Value::String(s) => {
+            buf.push(STRING);
+            put_str(buf, s);
+        }
+    }
+}
+
+/// Streaming decoder, reading records from an underlying reader.
+/// Reads are small, wrap unbuffered readers in a `std::io::BufReader`.
+///
+/// As an iterator, decoding stops after the first error.
+pub struct Decoder<R: Read> {
+    reader: R,
+    names: Vec<Box<str>>,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> std::fmt::Debug for Decoder<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Decoder")
+            .field("names", &self.names.len())
+            .finish()
+    }
+}
+
+impl<R: Read> Decoder<R> {
+    /// Begin decoding a stream from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            names: Vec::new(),
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Decode the next record, or None at the clean end of the stream.
+    pub fn decode(&mut self) -> Result<Option<OneErr>, OneErr> {
+        let mut first = self.read_first()?;
+        if !self.started {
+            match first {
+                None => return Ok(None),
+                Some(VERSION) => (),
+                Some(v) => {
+                    return Err(syntax(format!("unsupported version {}", v)))
+                }
+            }
+            self.started = true;
+            first = self.read_first()?;
+        }
+        let first = match first {
+            None => return Ok(None),
+            Some(first) => first,
+        };
+
+        let kind = match self.read_varint(first)? {
+            0 => self.read_str()?.into_boxed_str(),
+            tag => match tag_to_kind(tag) {
+                Some(kind) => kind.into(),
+                None => {
+                    return Err(syntax(format!("unknown kind tag {}", tag)))
+                }
+            },
+        };
+
+        let mut top_map = TopMap::new();
+        let count = self.read_u8().and_then(|b| self.read_varint(b))?;
+        for _ in 0..count {
+            let name = self.read_name()?;
+            let value = self.read_value()?;
+            top_map.insert(name, value);
+        }
+        top_map.insert(ERROR.into(), kind.into());
+
+        OneErr::priv_from_top_map(top_map).map(Some).map_err(syntax)
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_first(&mut self) -> Result<Option<u8>, OneErr> {
+        let mut b = [0];
+        loop {
+            match self.reader.read(&mut b) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(b[0])),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(io(e)),
+            }
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), OneErr> {
+        self.reader.read_exact(buf).map_err(io)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, OneErr> {
+        let mut b = [0];
+        self.read_exact(&mut b)?;
+        Ok(b[0])
+    }
+
+    fn read_varint(&mut self, first: u8) -> Result<u64, OneErr> {
+        let mut v = (first & 0x7f) as u64;
+        let mut b = first;
+        let mut shift = 7;
+        while b & 0x80 != 0 {
+            if shift > 63 {
+                return Err(syntax("varint overflow"));
+            }
+            b = self.read_u8()?;
+            v |= ((b & 0x7f) as u64) << shift;
+            shift += 7;
+        }
+        Ok(v)
+    }
+
+    fn read_bytes(&mut self, len: u64) -> Result<Vec<u8>, OneErr> {
+        // don't trust the length for allocation, read what is there
+        let mut buf = Vec::new();
+        (&mut self.reader)
+            .take(len)
+            .read_to_end(&mut buf)
+            .map_err(io)?;
+        if (buf.len() as u64) < len {
+            return Err(eof());
+        }
+        Ok(buf)
+    }
+
+    fn read_utf8(&mut self, len: u64) -> Result<String, OneErr> {
+        let buf = self.read_bytes(len)?;
+        String::from_utf8(buf).map_err(|_| syntax("invalid utf8 string"))
+    }
+
+    fn read_str(&mut self) -> Result<String, OneErr> {
+        let len = self.read_u8().and_then(|b| self.read_varint(b))?;
+        self.read_utf8(len)
+    }
+
+    fn read_name(&mut self) -> Result<Box<str>, OneErr> {
+        let v = self.read_u8().and_then(|b| self.read_varint(b))?;
+        if v & 1 == 1 {
+            return match self.names.get((v >> 1) as usize) {
+                Some(name) => Ok(name.clone()),
+                None => Err(syntax(format!("unknown name index {}", v >> 1))),
+            };
+        }
+        let name = self.read_utf8(v >> 1)?.into_boxed_str();
+        if self.names.len() < MAX_NAMES {
+            self.names.push(name.clone());
+        }
+        Ok(name)
+    }
+
+    fn read_value(&mut self) -> Result<Value, OneErr> {
+        Ok(match self.read_u8()? {
+            NULL => Value::Null,
+            FALSE => Value::Bool(false),
+            TRUE => Value::Bool(true),
+            I64 => {
+                let v = self.read_u8().and_then(|b| self.read_varint(b))?;
+                Value::I64((v >> 1) as i64 ^ -((v & 1) as i64))
+            }
+            U64 => {
+                Value::U64(self.read_u8().and_then(|b| self.read_varint(b))?)
+            }
+            F64 => {
+                let mut b = [0; 8];
+                self.read_exact(&mut b)?;
+                Value::F64(f64::from_le_bytes(b))
+            }
+            STRING => self.read_str()?.into(),
+            t => return Err(syntax(format!("unknown value type {}", t))),
+        })
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = Result<OneErr, OneErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.decode().transpose();
+        if !matches!(res, Some(Ok(_))) {
+            self.done = true;
+        }
+        res
+    }
+}
+
+fn decode_err(
+    kind: std::io::ErrorKind,
+    category: &str,
+    message: impl std::fmt::Display,
+) -> OneErr {
+    let mut err = OneErr::with_message(err_kind_to_str(kind), message);
+    err.set_field(FORMAT_FIELD, COMPACT)
+        .set_field(CATEGORY_FIELD, category);
+    err
+}
+
+fn syntax(message: impl std::fmt::Display) -> OneErr {
+    decode_err(std::io::ErrorKind::InvalidData, SYNTAX, message)
+}
+
+fn eof() -> OneErr {
+    decode_err(
+        std::io::ErrorKind::UnexpectedEof,
+        EOF,
+        "unexpected end of input",
+    )
+}
+
+fn io(e: std::io::Error) -> OneErr {
+    if e.kind() == std::io::ErrorKind::UnexpectedEof {
+        eof()
+    } else {
+        decode_err(std::io::ErrorKind::InvalidData, IO, e)
+    }
+}
+
+impl OneErr {
+    /// Encode this instance as a single record compact stream,
+    /// see [compact](crate::compact).
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let err = OneErr::with_message("NotFound", "no such thing");
+    /// let dec = OneErr::from_compact(&err.to_compact()).unwrap();
+    /// assert_eq!(err.to_string(), dec.to_string());
+    /// ```
+    pub fn to_compact(&self) -> Vec<u8> {
+        let mut enc = Encoder::new(Vec::new());
+        enc.encode(self).expect("encoding to a Vec cannot fail");
+        enc.into_inner()
+    }
+
+    /// Decode an instance from a single record compact stream.
+    pub fn from_compact(data: &[u8]) -> Result<Self, OneErr> {
+        let mut dec = Decoder::new(data);
+        let err = dec.decode()?.ok_or_else(eof)?;
+        if !dec.get_ref().is_empty() {
+            return Err(syntax("trailing data after record"));
+        }
+        Ok(err)
+    }
+}
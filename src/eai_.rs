@@ -0,0 +1,281 @@
+use crate::*;
+
+/// Rust translation of the getaddrinfo / getnameinfo EAI_* error codes.
+/// These live in a separate code space from errno values.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EaiErr {
+    /// Invalid value for ai_flags
+    BadFlags,
+
+    /// Name or service not known
+    NoName,
+
+    /// Temporary failure in name resolution
+    Again,
+
+    /// Non-recoverable failure in name resolution
+    Fail,
+
+    /// No address associated with hostname
+    NoData,
+
+    /// ai_family not supported
+    Family,
+
+    /// ai_socktype not supported
+    SockType,
+
+    /// Service not supported for ai_socktype
+    Service,
+
+    /// Memory allocation failure
+    Memory,
+
+    /// System error, the actual error is in errno
+    System,
+
+    /// Argument buffer overflow
+    Overflow,
+
+    /// An EAI code not in this list
+    Other,
+}
+
+impl std::fmt::Display for EaiErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(<&'static str>::from(self))
+    }
+}
+
+#[cfg(unix)]
+impl From<i32> for EaiErr {
+    fn from(e: i32) -> Self {
+        match e {
+            libc::EAI_BADFLAGS => Self::BadFlags,
+            libc::EAI_NONAME => Self::NoName,
+            libc::EAI_AGAIN => Self::Again,
+            libc::EAI_FAIL => Self::Fail,
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "ios"
+            ))]
+            libc::EAI_NODATA => Self::NoData,
+            libc::EAI_FAMILY => Self::Family,
+            libc::EAI_SOCKTYPE => Self::SockType,
+            libc::EAI_SERVICE => Self::Service,
+            libc::EAI_MEMORY => Self::Memory,
+            libc::EAI_SYSTEM => Self::System,
+            libc::EAI_OVERFLOW => Self::Overflow,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl EaiErr {
+    /// Might the lookup succeed if retried later?
+    /// `OneErr` cannot express this through `io_kind()` or `errno()`,
+    /// so retry logic should check `err.eai().is_transient()` as well.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Again)
+    }
+
+    /// The EAI code for the current target, if it defines one.
+    #[cfg(unix)]
+    pub fn code(&self) -> Option<i32> {
+        Some(match self {
+            Self::BadFlags => libc::EAI_BADFLAGS,
+            Self::NoName => libc::EAI_NONAME,
+            Self::Again => libc::EAI_AGAIN,
+            Self::Fail => libc::EAI_FAIL,
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "ios"
+            ))]
+            Self::NoData => libc::EAI_NODATA,
+            Self::Family => libc::EAI_FAMILY,
+            Self::SockType => libc::EAI_SOCKTYPE,
+            Self::Service => libc::EAI_SERVICE,
+            Self::Memory => libc::EAI_MEMORY,
+            Self::System => libc::EAI_SYSTEM,
+            Self::Overflow => libc::EAI_OVERFLOW,
+            #[allow(unreachable_patterns)]
+            _ => return None,
+        })
+    }
+}
+
+impl From<EaiErr> for std::io::ErrorKind {
+    fn from(e: EaiErr) -> Self {
+        (&e).into()
+    }
+}
+
+impl From<&EaiErr> for std::io::ErrorKind {
+    fn from(e: &EaiErr) -> Self {
+        use std::io::ErrorKind::*;
+        match e {
+            // transient, the lookup may succeed if retried
+            EaiErr::Again => TimedOut,
+            EaiErr::NoName => NotFound,
+            EaiErr::NoData => NotFound,
+            EaiErr::BadFlags => InvalidInput,
+            EaiErr::Family => InvalidInput,
+            EaiErr::SockType => InvalidInput,
+            EaiErr::Service => InvalidInput,
+            _ => Other,
+        }
+    }
+}
+
+impl From<EaiErr> for OneErr {
+    #[track_caller]
+    fn from(e: EaiErr) -> Self {
+        if let EaiErr::Other = e {
+            OneErr::from(std::io::ErrorKind::Other)
+        } else {
+            OneErr::new(e)
+        }
+    }
+}
+
+/// Check the return code of getaddrinfo / getnameinfo.
+/// Zero is success. EAI_SYSTEM defers to the os errno. A code not in
+/// [EaiErr] is kept in an [EAI_CODE_FIELD](eai::EAI_CODE_FIELD) field.
+#[cfg(unix)]
+#[track_caller]
+pub fn check_eai(ret: libc::c_int) -> Result<(), OneErr> {
+    match ret {
+        0 => Ok(()),
+        libc::EAI_SYSTEM => Err(OneErr::last_os_error()),
+        _ => match EaiErr::from(ret) {
+            EaiErr::Other => {
+                let mut err = OneErr::from(EaiErr::Other);
+                err.set_field(EAI_CODE_FIELD, ret as i64);
+                Err(err)
+            }
+            eai => Err(eai.into()),
+        },
+    }
+}
+
+impl OneErr {
+    /// Get the EaiErr associated with this instance, if any.
+    /// EAI kinds are not errnos, their `io_kind()` and `errno()` are
+    /// `Other`, use `std::io::ErrorKind::from(err.eai())` to classify them,
+    /// and [EaiErr::is_transient] to decide whether to retry.
+    pub fn eai(&self) -> EaiErr {
+        EaiErr::from(self.str_kind())
+    }
+}
+
+/// Constants for working with EaiErr `str_kind()`.
+pub mod eai {
+    /// Field holding the raw code of an EAI error not in [EaiErr](crate::EaiErr).
+    pub const EAI_CODE_FIELD: &str = "eai_code";
+
+    /// The const str 'EAI_BADFLAGS'.
+    pub const EAI_BADFLAGS_STR: &str = "EAI_BADFLAGS";
+
+    /// The const str 'EAI_NONAME'.
+    pub const EAI_NONAME_STR: &str = "EAI_NONAME";
+
+    /// The const str 'EAI_AGAIN'.
+    pub const EAI_AGAIN_STR: &str = "EAI_AGAIN";
+
+    /// The const str 'EAI_FAIL'.
+    pub const EAI_FAIL_STR: &str = "EAI_FAIL";
+
+    /// The const str 'EAI_NODATA'.
+    pub const EAI_NODATA_STR: &str = "EAI_NODATA";
+
+    /// The const str 'EAI_FAMILY'.
+    pub const EAI_FAMILY_STR: &str = "EAI_FAMILY";
+
+    /// The const str 'EAI_SOCKTYPE'.
+    pub const EAI_SOCKTYPE_STR: &str = "EAI_SOCKTYPE";
+
+    /// The const str 'EAI_SERVICE'.
+    pub const EAI_SERVICE_STR: &str = "EAI_SERVICE";
+
+    /// The const str 'EAI_MEMORY'.
+    pub const EAI_MEMORY_STR: &str = "EAI_MEMORY";
+
+    /// The const str 'EAI_SYSTEM'.
+    pub const EAI_SYSTEM_STR: &str = "EAI_SYSTEM";
+
+    /// The const str 'EAI_OVERFLOW'.
+    pub const EAI_OVERFLOW_STR: &str = "EAI_OVERFLOW";
+
+    /// The const str 'EAI_OTHER'.
+    pub const EAI_OTHER_STR: &str = "EAI_OTHER";
+}
+use eai::*;
+
+impl From<EaiErr> for &'static str {
+    fn from(e: EaiErr) -> &'static str {
+        (&e).into()
+    }
+}
+
+impl From<&EaiErr> for &'static str {
+    fn from(e: &EaiErr) -> &'static str {
+        match *e {
+            EaiErr::BadFlags => EAI_BADFLAGS_STR,
+            EaiErr::NoName => EAI_NONAME_STR,
+            EaiErr::Again => EAI_AGAIN_STR,
+            EaiErr::Fail => EAI_FAIL_STR,
+            EaiErr::NoData => EAI_NODATA_STR,
+            EaiErr::Family => EAI_FAMILY_STR,
+            EaiErr::SockType => EAI_SOCKTYPE_STR,
+            EaiErr::Service => EAI_SERVICE_STR,
+            EaiErr::Memory => EAI_MEMORY_STR,
+            EaiErr::System => EAI_SYSTEM_STR,
+            EaiErr::Overflow => EAI_OVERFLOW_STR,
+            EaiErr::Other => EAI_OTHER_STR,
+        }
+    }
+}
+
+impl From<&str> for EaiErr {
+    fn from(s: &str) -> Self {
+        match s {
+            EAI_BADFLAGS_STR => Self::BadFlags,
+            EAI_NONAME_STR => Self::NoName,
+            EAI_AGAIN_STR => Self::Again,
+            EAI_FAIL_STR => Self::Fail,
+            EAI_NODATA_STR => Self::NoData,
+            EAI_FAMILY_STR => Self::Family,
+            EAI_SOCKTYPE_STR => Self::SockType,
+            EAI_SERVICE_STR => Self::Service,
+            EAI_MEMORY_STR => Self::Memory,
+            EAI_SYSTEM_STR => Self::System,
+            EAI_OVERFLOW_STR => Self::Overflow,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl serde::Serialize for EaiErr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let r: &'static str = self.into();
+        serializer.serialize_str(r)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EaiErr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let e: &'de str = serde::Deserialize::deserialize(deserializer)?;
+        Ok(e.into())
+    }
+}
@@ -16,6 +16,17 @@ const EDEADLOCK_1: i32 = libc::EDEADLK;
 )))]
 const EDEADLOCK_2: i32 = libc::EDEADLOCK;
 
+// the windows C runtime does not define these,
+// give them codes no errno there collides with
+#[cfg(unix)]
+const ESTALE: i32 = libc::ESTALE;
+#[cfg(not(unix))]
+const ESTALE: i32 = -116;
+#[cfg(unix)]
+const EDQUOT: i32 = libc::EDQUOT;
+#[cfg(not(unix))]
+const EDQUOT: i32 = -122;
+
 // incase we get an errno not in our list
 const EOTHER: i32 = -1;
 
@@ -347,8 +358,8 @@ pub enum ErrNo {
     /// Operation now in progress
     InProgress = libc::EINPROGRESS,
 
-    // /// Stale NFS file handle
-    // Stale = libc::ESTALE,
+    /// Stale file handle
+    Stale = ESTALE,
 
     // /// Structure needs cleaning
     // UClean = libc::EUCLEAN,
@@ -364,13 +375,613 @@ pub enum ErrNo {
 
     // /// Remote I/O error
     // RemoteIO = libc::EREMOTEIO,
+    /// Disk quota exceeded
+    DQuot = EDQUOT,
+
     /// Other / Unrecognized Error
     Other = EOTHER,
 }
 
 impl std::fmt::Display for ErrNo {
+    /// The alternate form `{:#}` includes the description,
+    /// e.g. "ENOENT (No such file or directory)".
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(<&'static str>::from(self))
+        f.write_str(<&'static str>::from(self))?;
+        if f.alternate() {
+            write!(f, " ({})", self.description())?;
+        }
+        Ok(())
+    }
+}
+
+// bit flags for ErrNoInfo::class
+const NET: u8 = 0b0001;
+const FS: u8 = 0b0010;
+const RES: u8 = 0b0100;
+const TRANSIENT: u8 = 0b1000;
+
+struct ErrNoInfo {
+    errno: ErrNo,
+    name: &'static str,
+    description: &'static str,
+    class: u8,
+}
+
+// the single source of truth for the name, description, and class of
+// each ErrNo. The numeric value comes from the enum discriminant.
+const ERRNO_TABLE: &[ErrNoInfo] = &[
+    ErrNoInfo {
+        errno: ErrNo::Perm,
+        name: EPERM_STR,
+        description: "Permission denied",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoEnt,
+        name: ENOENT_STR,
+        description: "No such file or directory",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Srch,
+        name: ESRCH_STR,
+        description: "No such process",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Intr,
+        name: EINTR_STR,
+        description: "Interrupted system call",
+        class: TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Io,
+        name: EIO_STR,
+        description: "I/O error",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NxIo,
+        name: ENXIO_STR,
+        description: "No such device or address",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::TooBig,
+        name: E2BIG_STR,
+        description: "Arg list too long",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoExec,
+        name: ENOEXEC_STR,
+        description: "Exec format error",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::BadF,
+        name: EBADF_STR,
+        description: "Bad file number",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Child,
+        name: ECHILD_STR,
+        description: "No child processes",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoMem,
+        name: ENOMEM_STR,
+        description: "Out of memory",
+        class: RES | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Fault,
+        name: EFAULT_STR,
+        description: "Bad address",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Busy,
+        name: EBUSY_STR,
+        description: "Device or resource busy",
+        class: TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Exist,
+        name: EEXIST_STR,
+        description: "File exists",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::XDev,
+        name: EXDEV_STR,
+        description: "Cross-device link",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoDev,
+        name: ENODEV_STR,
+        description: "No such device",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NotDir,
+        name: ENOTDIR_STR,
+        description: "Not a directory",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::IsDir,
+        name: EISDIR_STR,
+        description: "Is a directory",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Inval,
+        name: EINVAL_STR,
+        description: "Invalid argument",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NFile,
+        name: ENFILE_STR,
+        description: "File table overflow",
+        class: FS | RES,
+    },
+    ErrNoInfo {
+        errno: ErrNo::MFile,
+        name: EMFILE_STR,
+        description: "Too many open files",
+        class: FS | RES,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NotTy,
+        name: ENOTTY_STR,
+        description: "Not a typewriter",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::TxtBsy,
+        name: ETXTBSY_STR,
+        description: "Text file busy",
+        class: FS | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::FBig,
+        name: EFBIG_STR,
+        description: "File too large",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoSpc,
+        name: ENOSPC_STR,
+        description: "No space left on device",
+        class: FS | RES,
+    },
+    ErrNoInfo {
+        errno: ErrNo::SPipe,
+        name: ESPIPE_STR,
+        description: "Illegal seek",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ROFS,
+        name: EROFS_STR,
+        description: "Read-only file system",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::MLink,
+        name: EMLINK_STR,
+        description: "Too many links",
+        class: FS | RES,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Pipe,
+        name: EPIPE_STR,
+        description: "Broken pipe",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Dom,
+        name: EDOM_STR,
+        description: "Math argument out of domain of func",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Range,
+        name: ERANGE_STR,
+        description: "Math result not representable",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::DeadLk,
+        name: EDEADLK_STR,
+        description: "Resource deadlock would occur",
+        class: TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NameTooLong,
+        name: ENAMETOOLONG_STR,
+        description: "File name too long",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoLck,
+        name: ENOLCK_STR,
+        description: "No record locks available",
+        class: FS | RES,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoSys,
+        name: ENOSYS_STR,
+        description: "Function not implemented",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NotEmpty,
+        name: ENOTEMPTY_STR,
+        description: "Directory not empty",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Loop,
+        name: ELOOP_STR,
+        description: "Too many symbolic links encountered",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::WouldBlock,
+        name: EWOULDBLOCK_STR,
+        description: "Operation would block",
+        class: TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoMsg,
+        name: ENOMSG_STR,
+        description: "No message of desired type",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::IdRm,
+        name: EIDRM_STR,
+        description: "Identifier removed",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoStr,
+        name: ENOSTR_STR,
+        description: "Device not a stream",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoData,
+        name: ENODATA_STR,
+        description: "No data available",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Time,
+        name: ETIME_STR,
+        description: "Timer expired",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoSR,
+        name: ENOSR_STR,
+        description: "Out of streams resources",
+        class: RES | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoLink,
+        name: ENOLINK_STR,
+        description: "Link has been severed",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Proto,
+        name: EPROTO_STR,
+        description: "Protocol error",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::BadMsg,
+        name: EBADMSG_STR,
+        description: "Not a data message",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Overflow,
+        name: EOVERFLOW_STR,
+        description: "Value too large for defined data type",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::IlSeq,
+        name: EILSEQ_STR,
+        description: "Illegal byte sequence",
+        class: 0,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NotSock,
+        name: ENOTSOCK_STR,
+        description: "Socket operation on non-socket",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::DestAddrReq,
+        name: EDESTADDRREQ_STR,
+        description: "Destination address required",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::MsgSize,
+        name: EMSGSIZE_STR,
+        description: "Message too long",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ProtoType,
+        name: EPROTOTYPE_STR,
+        description: "Protocol wrong type for socket",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoProtoOpt,
+        name: ENOPROTOOPT_STR,
+        description: "Protocol not available",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ProtoNoSupport,
+        name: EPROTONOSUPPORT_STR,
+        description: "Protocol not supported",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::OpNotSupp,
+        name: EOPNOTSUPP_STR,
+        description: "Operation not supported on transport endpoint",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::AFNoSupport,
+        name: EAFNOSUPPORT_STR,
+        description: "Address family not supported by protocol",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::AddrInUse,
+        name: EADDRINUSE_STR,
+        description: "Address already in use",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::AddrNotAvail,
+        name: EADDRNOTAVAIL_STR,
+        description: "Cannot assign requested address",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NetDown,
+        name: ENETDOWN_STR,
+        description: "Network is down",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NetUnreach,
+        name: ENETUNREACH_STR,
+        description: "Network is unreachable",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NetReset,
+        name: ENETRESET_STR,
+        description: "Network dropped connection because of reset",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ConnAborted,
+        name: ECONNABORTED_STR,
+        description: "Software caused connection abort",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ConnReset,
+        name: ECONNRESET_STR,
+        description: "Connection reset by peer",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NoBufS,
+        name: ENOBUFS_STR,
+        description: "No buffer space available",
+        class: NET | RES | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::IsConn,
+        name: EISCONN_STR,
+        description: "Transport endpoint is already connected",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::NotConn,
+        name: ENOTCONN_STR,
+        description: "Transport endpoint is not connected",
+        class: NET,
+    },
+    ErrNoInfo {
+        errno: ErrNo::TimedOut,
+        name: ETIMEDOUT_STR,
+        description: "Connection timed out",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::ConnRefused,
+        name: ECONNREFUSED_STR,
+        description: "Connection refused",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::HostUnreach,
+        name: EHOSTUNREACH_STR,
+        description: "No route to host",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Already,
+        name: EALREADY_STR,
+        description: "Operation already in progress",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::InProgress,
+        name: EINPROGRESS_STR,
+        description: "Operation now in progress",
+        class: NET | TRANSIENT,
+    },
+    ErrNoInfo {
+        errno: ErrNo::Stale,
+        name: ESTALE_STR,
+        description: "Stale file handle",
+        class: FS,
+    },
+    ErrNoInfo {
+        errno: ErrNo::DQuot,
+        name: EDQUOT_STR,
+        description: "Disk quota exceeded",
+        class: FS | RES,
+    },
+];
+
+// indices into ERRNO_TABLE sorted by errno code and by name,
+// so the conversions can binary search rather than scan the table
+const BY_CODE: [u8; ERRNO_TABLE.len()] = sort_table(false);
+const BY_NAME: [u8; ERRNO_TABLE.len()] = sort_table(true);
+
+const fn sort_table(by_name: bool) -> [u8; ERRNO_TABLE.len()] {
+    let mut out = [0; ERRNO_TABLE.len()];
+    let mut i = 0;
+    while i < out.len() {
+        out[i] = i as u8;
+        i += 1;
+    }
+    // insertion sort, const fns cannot call slice::sort
+    let mut i = 1;
+    while i < out.len() {
+        let mut j = i;
+        while j > 0 {
+            let a = &ERRNO_TABLE[out[j] as usize];
+            let b = &ERRNO_TABLE[out[j - 1] as usize];
+            let less = if by_name {
+                str_less(a.name, b.name)
+            } else {
+                (a.errno as i32) < (b.errno as i32)
+            };
+            if !less {
+                break;
+            }
+            let t = out[j];
+            out[j] = out[j - 1];
+            out[j - 1] = t;
+            j -= 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+const fn str_less(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+        i += 1;
+    }
+    a.len() < b.len()
+}
+
+fn find_code(code: i32) -> Option<&'static ErrNoInfo> {
+    BY_CODE
+        .binary_search_by_key(&code, |i| ERRNO_TABLE[*i as usize].errno as i32)
+        .ok()
+        .map(|i| &ERRNO_TABLE[BY_CODE[i] as usize])
+}
+
+fn find_name(name: &str) -> Option<&'static ErrNoInfo> {
+    BY_NAME
+        .binary_search_by(|i| ERRNO_TABLE[*i as usize].name.cmp(name))
+        .ok()
+        .map(|i| &ERRNO_TABLE[BY_NAME[i] as usize])
+}
+
+const OTHER_INFO: ErrNoInfo = ErrNoInfo {
+    errno: ErrNo::Other,
+    name: EOTHER_STR,
+    description: "Other / Unrecognized Error",
+    class: 0,
+};
+
+impl ErrNo {
+    /// Iterate over all known ErrNo values (excluding `ErrNo::Other`).
+    pub fn all() -> impl Iterator<Item = ErrNo> {
+        ERRNO_TABLE.iter().map(|i| i.errno)
+    }
+
+    fn info(&self) -> &'static ErrNoInfo {
+        find_code(*self as i32).unwrap_or(&OTHER_INFO)
+    }
+
+    /// A portable, locale-independent description of this errno.
+    pub fn description(&self) -> &'static str {
+        self.info().description
+    }
+
+    /// Is this a networking / socket errno?
+    pub fn is_network(&self) -> bool {
+        self.info().class & NET != 0
+    }
+
+    /// Is this a filesystem / file descriptor errno?
+    pub fn is_filesystem(&self) -> bool {
+        self.info().class & FS != 0
+    }
+
+    /// Does this errno indicate some resource or limit was exhausted?
+    pub fn is_resource_exhaustion(&self) -> bool {
+        self.info().class & RES != 0
+    }
+
+    /// Might the operation succeed if retried later?
+    pub fn is_transient(&self) -> bool {
+        self.info().class & TRANSIENT != 0
+    }
+
+    /// The description of this errno from the os `strerror_r`,
+    /// which may be translated for the current locale.
+    /// Returns None for `ErrNo::Other` or if the lookup fails.
+    #[cfg(unix)]
+    #[allow(unsafe_code)]
+    pub fn strerror(&self) -> Option<String> {
+        if let Self::Other = self {
+            return None;
+        }
+        let mut buf = [0 as libc::c_char; 256];
+        // safety: buf is valid for writes of buf.len() bytes,
+        // and strerror_r nul terminates on success
+        let res = unsafe {
+            libc::strerror_r(i32::from(self), buf.as_mut_ptr(), buf.len())
+        };
+        if res != 0 {
+            return None;
+        }
+        // safety: strerror_r wrote a nul terminated string into buf
+        let s = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+        Some(s.to_string_lossy().into_owned())
     }
 }
 
@@ -388,136 +999,18 @@ impl From<&ErrNo> for i32 {
 
 impl From<i32> for ErrNo {
     fn from(e: i32) -> Self {
+        if let Some(info) = find_code(e) {
+            return info.errno;
+        }
         match e {
-            libc::ENOENT => Self::NoEnt,
-            libc::ESRCH => Self::Srch,
-            libc::EINTR => Self::Intr,
-            libc::EIO => Self::Io,
-            libc::ENXIO => Self::NxIo,
-            libc::E2BIG => Self::TooBig,
-            libc::ENOEXEC => Self::NoExec,
-            libc::EBADF => Self::BadF,
-            libc::ECHILD => Self::Child,
-            libc::ENOMEM => Self::NoMem,
-            libc::EFAULT => Self::Fault,
-            // libc::ENOTBLK => Self::NotBlk,
-            libc::EBUSY => Self::Busy,
-            libc::EEXIST => Self::Exist,
-            libc::EXDEV => Self::XDev,
-            libc::ENODEV => Self::NoDev,
-            libc::ENOTDIR => Self::NotDir,
-            libc::EISDIR => Self::IsDir,
-            libc::EINVAL => Self::Inval,
-            libc::ENFILE => Self::NFile,
-            libc::EMFILE => Self::MFile,
-            libc::ENOTTY => Self::NotTy,
-            libc::ETXTBSY => Self::TxtBsy,
-            libc::EFBIG => Self::FBig,
-            libc::ENOSPC => Self::NoSpc,
-            libc::ESPIPE => Self::SPipe,
-            libc::EROFS => Self::ROFS,
-            libc::EMLINK => Self::MLink,
-            libc::EPIPE => Self::Pipe,
-            libc::EDOM => Self::Dom,
-            libc::ERANGE => Self::Range,
-            libc::ENAMETOOLONG => Self::NameTooLong,
-            libc::ENOLCK => Self::NoLck,
-            libc::ENOSYS => Self::NoSys,
-            libc::ENOTEMPTY => Self::NotEmpty,
-            libc::ELOOP => Self::Loop,
-            libc::ENOMSG => Self::NoMsg,
-            libc::EIDRM => Self::IdRm,
-            //libc::ECHRNG => Self::ChRng,
-            //libc::EL2NSYNC => Self::L2NSync,
-            //libc::EL3HLT => Self::L3Hlt,
-            //libc::EL3RST => Self::L3Rst,
-            //libc::ELNRNG => Self::LNRng,
-            //libc::EUNATCH => Self::Unatch,
-            //libc::ENOCSI => Self::NoCSI,
-            //libc::EL2HLT => Self::L2Hlt,
-            //libc::EBADE => Self::BadE,
-            //libc::EBADR => Self::BadR,
-            //libc::EXFULL => Self::XFull,
-            //libc::ENOANO => Self::NoAno,
-            //libc::EBADRQC => Self::BadRqC,
-            //libc::EBADSLT => Self::BadSlt,
-            //libc::EBFONT => Self::BFont,
-            libc::ENOSTR => Self::NoStr,
-            libc::ENODATA => Self::NoData,
-            libc::ETIME => Self::Time,
-            libc::ENOSR => Self::NoSR,
-            //libc::ENONET => Self::NoNet,
-            //libc::ENOPKG => Self::NoPkg,
-            //libc::EREMOTE => Self::Remote,
-            libc::ENOLINK => Self::NoLink,
-            //libc::EADV => Self::Adv,
-            //libc::ESRMNT => Self::SrMnt,
-            //libc::ECOMM => Self::Comm,
-            libc::EPROTO => Self::Proto,
-            //libc::EMULTIHOP => Self::MultiHop,
-            //libc::EDOTDOT => Self::DotDot,
-            libc::EBADMSG => Self::BadMsg,
-            libc::EOVERFLOW => Self::Overflow,
-            //libc::ENOTUNIQ => Self::NotUniq,
-            //libc::EBADFD => Self::BadFD,
-            //libc::EREMCHG => Self::RemChg,
-            //libc::ELIBACC => Self::LibAcc,
-            //libc::ELIBBAD => Self::LibBad,
-            //libc::ELIBSCN => Self::LibScn,
-            //libc::ELIBMAX => Self::LibMax,
-            //libc::ELIBEXEC => Self::LibExec,
-            libc::EILSEQ => Self::IlSeq,
-            //libc::ERESTART => Self::Restart,
-            //libc::ESTRPIPE => Self::StrPipe,
-            //libc::EUSERS => Self::Users,
-            libc::ENOTSOCK => Self::NotSock,
-            libc::EDESTADDRREQ => Self::DestAddrReq,
-            libc::EMSGSIZE => Self::MsgSize,
-            libc::EPROTOTYPE => Self::ProtoType,
-            libc::ENOPROTOOPT => Self::NoProtoOpt,
-            libc::EPROTONOSUPPORT => Self::ProtoNoSupport,
-            //libc::ESOCKTNOSUPPORT => Self::SockTNoSupport,
-            libc::EOPNOTSUPP => Self::OpNotSupp,
-            //libc::EPFNOSUPPORT => Self::PFNoSupport,
-            libc::EAFNOSUPPORT => Self::AFNoSupport,
-            libc::EADDRINUSE => Self::AddrInUse,
-            libc::EADDRNOTAVAIL => Self::AddrNotAvail,
-            libc::ENETDOWN => Self::NetDown,
-            libc::ENETUNREACH => Self::NetUnreach,
-            libc::ENETRESET => Self::NetReset,
-            libc::ECONNABORTED => Self::ConnAborted,
-            libc::ECONNRESET => Self::ConnReset,
-            libc::ENOBUFS => Self::NoBufS,
-            libc::EISCONN => Self::IsConn,
-            libc::ENOTCONN => Self::NotConn,
-            //libc::ESHUTDOWN => Self::Shutdown,
-            //libc::ETOOMANYREFS => Self::TooManyRefs,
-            libc::ETIMEDOUT => Self::TimedOut,
-            libc::ECONNREFUSED => Self::ConnRefused,
-            //libc::EHOSTDOWN => Self::HostDown,
-            libc::EHOSTUNREACH => Self::HostUnreach,
-            libc::EALREADY => Self::Already,
-            libc::EINPROGRESS => Self::InProgress,
-            //libc::ESTALE => Self::Stale,
-            //libc::EUCLEAN => Self::UClean,
-            //libc::ENOTNAM => Self::NotNam,
-            //libc::ENAVAIL => Self::NAvail,
-            //libc::EISNAM => Self::IsNam,
-            //libc::EREMOTEIO => Self::RemoteIO,
-            x if x == EPERM_1 || x == EPERM_2 => Self::Perm,
-            #[cfg(any(
-                target_os = "macos",
-                target_os = "android",
-                target_os = "ios"
-            ))]
-            x if x == EDEADLOCK_1 => Self::DeadLk,
+            x if x == EPERM_2 => Self::Perm,
             #[cfg(not(any(
                 target_os = "macos",
                 target_os = "android",
                 target_os = "ios"
             )))]
-            x if x == EDEADLOCK_1 || x == EDEADLOCK_2 => Self::DeadLk,
-            x if x == EWOULDBLOCK_1 || x == EWOULDBLOCK_2 => Self::WouldBlock,
+            x if x == EDEADLOCK_2 => Self::DeadLk,
+            x if x == EWOULDBLOCK_2 => Self::WouldBlock,
             _ => Self::Other,
         }
     }
@@ -530,6 +1023,8 @@ impl From<ErrNo> for std::io::ErrorKind {
 }
 
 impl From<&ErrNo> for std::io::ErrorKind {
+    /// Matches the decoding of `std::io::Error::from_raw_os_error`.
+    /// Errnos std decodes into unstable kinds (ELOOP, EINPROGRESS) are Other.
     fn from(e: &ErrNo) -> Self {
         use std::io::ErrorKind::*;
         match e {
@@ -543,14 +1038,33 @@ impl From<&ErrNo> for std::io::ErrorKind {
             ErrNo::Intr => Interrupted,
             ErrNo::Inval => InvalidInput,
             ErrNo::NoEnt => NotFound,
-            //#[cfg(version("1.54"))]
-            //ErrNo::NoMem => OutOfMemory,
-            //#[cfg(version("1.53"))]
-            //ErrNo::NoSys => Unsupported,
+            ErrNo::NoMem => OutOfMemory,
+            ErrNo::NoSys => Unsupported,
             ErrNo::NotConn => NotConnected,
             ErrNo::Pipe => BrokenPipe,
             ErrNo::WouldBlock => WouldBlock,
             ErrNo::TimedOut => TimedOut,
+            ErrNo::TooBig => ArgumentListTooLong,
+            ErrNo::Busy => ResourceBusy,
+            ErrNo::XDev => CrossesDevices,
+            ErrNo::NotDir => NotADirectory,
+            ErrNo::IsDir => IsADirectory,
+            ErrNo::TxtBsy => ExecutableFileBusy,
+            ErrNo::FBig => FileTooLarge,
+            ErrNo::NoSpc => StorageFull,
+            ErrNo::SPipe => NotSeekable,
+            ErrNo::ROFS => ReadOnlyFilesystem,
+            ErrNo::MLink => TooManyLinks,
+            ErrNo::DeadLk => Deadlock,
+            ErrNo::NameTooLong => InvalidFilename,
+            ErrNo::NotEmpty => DirectoryNotEmpty,
+            ErrNo::NetDown => NetworkDown,
+            ErrNo::NetUnreach => NetworkUnreachable,
+            ErrNo::HostUnreach => HostUnreachable,
+            ErrNo::Stale => StaleNetworkFileHandle,
+            ErrNo::DQuot => QuotaExceeded,
+            // std also decodes EOPNOTSUPP as Unsupported
+            ErrNo::OpNotSupp => Unsupported,
             _ => Other,
         }
     }
@@ -576,14 +1090,31 @@ impl From<&std::io::ErrorKind> for ErrNo {
             Interrupted => ErrNo::Intr,
             InvalidInput => ErrNo::Inval,
             NotFound => ErrNo::NoEnt,
-            //#[cfg(version("1.54"))]
-            //OutOfMemory => ErrNo::NoMem,
-            //#[cfg(version("1.53"))]
-            //Unsupported => ErrNo::NoSys,
+            OutOfMemory => ErrNo::NoMem,
+            Unsupported => ErrNo::NoSys,
             NotConnected => ErrNo::NotConn,
             BrokenPipe => ErrNo::Pipe,
             WouldBlock => ErrNo::WouldBlock,
             TimedOut => ErrNo::TimedOut,
+            ArgumentListTooLong => ErrNo::TooBig,
+            ResourceBusy => ErrNo::Busy,
+            CrossesDevices => ErrNo::XDev,
+            NotADirectory => ErrNo::NotDir,
+            IsADirectory => ErrNo::IsDir,
+            ExecutableFileBusy => ErrNo::TxtBsy,
+            FileTooLarge => ErrNo::FBig,
+            StorageFull => ErrNo::NoSpc,
+            NotSeekable => ErrNo::SPipe,
+            ReadOnlyFilesystem => ErrNo::ROFS,
+            TooManyLinks => ErrNo::MLink,
+            Deadlock => ErrNo::DeadLk,
+            InvalidFilename => ErrNo::NameTooLong,
+            DirectoryNotEmpty => ErrNo::NotEmpty,
+            NetworkDown => ErrNo::NetDown,
+            NetworkUnreachable => ErrNo::NetUnreach,
+            HostUnreachable => ErrNo::HostUnreach,
+            StaleNetworkFileHandle => ErrNo::Stale,
+            QuotaExceeded => ErrNo::DQuot,
             _ => ErrNo::Other,
         }
     }
@@ -927,8 +1458,8 @@ pub mod errno {
     /// The const str 'EINPROGRESS'.
     pub const EINPROGRESS_STR: &str = "EINPROGRESS";
 
-    // /// The const str 'ESTALE'.
-    // pub const ESTALE_STR: &str = "ESTALE";
+    /// The const str 'ESTALE'.
+    pub const ESTALE_STR: &str = "ESTALE";
 
     // /// The const str 'EUCLEAN'.
     // pub const EUCLEAN_STR: &str = "EUCLEAN";
@@ -945,6 +1476,9 @@ pub mod errno {
     // /// The const str 'EREMOTEIO'.
     // pub const EREMOTEIO_STR: &str = "EREMOTEIO";
 
+    /// The const str 'EDQUOT'.
+    pub const EDQUOT_STR: &str = "EDQUOT";
+
     /// The const str 'EOTHER'.
     pub const EOTHER_STR: &str = "EOTHER";
 }
@@ -958,252 +1492,15 @@ impl From<ErrNo> for &'static str {
 
 impl From<&ErrNo> for &'static str {
     fn from(e: &ErrNo) -> &'static str {
-        match *e {
-            ErrNo::Perm => EPERM_STR,
-            ErrNo::NoEnt => ENOENT_STR,
-            ErrNo::Srch => ESRCH_STR,
-            ErrNo::Intr => EINTR_STR,
-            ErrNo::Io => EIO_STR,
-            ErrNo::NxIo => ENXIO_STR,
-            ErrNo::TooBig => E2BIG_STR,
-            ErrNo::NoExec => ENOEXEC_STR,
-            ErrNo::BadF => EBADF_STR,
-            ErrNo::Child => ECHILD_STR,
-            ErrNo::NoMem => ENOMEM_STR,
-            ErrNo::Fault => EFAULT_STR,
-            // ErrNo::NotBlk => ENOTBLK_STR,
-            ErrNo::Busy => EBUSY_STR,
-            ErrNo::Exist => EEXIST_STR,
-            ErrNo::XDev => EXDEV_STR,
-            ErrNo::NoDev => ENODEV_STR,
-            ErrNo::NotDir => ENOTDIR_STR,
-            ErrNo::IsDir => EISDIR_STR,
-            ErrNo::Inval => EINVAL_STR,
-            ErrNo::NFile => ENFILE_STR,
-            ErrNo::MFile => EMFILE_STR,
-            ErrNo::NotTy => ENOTTY_STR,
-            ErrNo::TxtBsy => ETXTBSY_STR,
-            ErrNo::FBig => EFBIG_STR,
-            ErrNo::NoSpc => ENOSPC_STR,
-            ErrNo::SPipe => ESPIPE_STR,
-            ErrNo::ROFS => EROFS_STR,
-            ErrNo::MLink => EMLINK_STR,
-            ErrNo::Pipe => EPIPE_STR,
-            ErrNo::Dom => EDOM_STR,
-            ErrNo::Range => ERANGE_STR,
-            ErrNo::DeadLk => EDEADLK_STR,
-            ErrNo::NameTooLong => ENAMETOOLONG_STR,
-            ErrNo::NoLck => ENOLCK_STR,
-            ErrNo::NoSys => ENOSYS_STR,
-            ErrNo::NotEmpty => ENOTEMPTY_STR,
-            ErrNo::Loop => ELOOP_STR,
-            ErrNo::WouldBlock => EWOULDBLOCK_STR,
-            ErrNo::NoMsg => ENOMSG_STR,
-            ErrNo::IdRm => EIDRM_STR,
-            //ErrNo::ChRng => ECHRNG_STR,
-            //ErrNo::L2NSync => EL2NSYNC_STR,
-            //ErrNo::L3Hlt => EL3HLT_STR,
-            //ErrNo::L3Rst => EL3RST_STR,
-            //ErrNo::LNRng => ELNRNG_STR,
-            //ErrNo::Unatch => EUNATCH_STR,
-            //ErrNo::NoCSI => ENOCSI_STR,
-            //ErrNo::L2Hlt => EL2HLT_STR,
-            //ErrNo::BadE => EBADE_STR,
-            //ErrNo::BadR => EBADR_STR,
-            //ErrNo::XFull => EXFULL_STR,
-            //ErrNo::NoAno => ENOANO_STR,
-            //ErrNo::BadRqC => EBADRQC_STR,
-            //ErrNo::BadSlt => EBADSLT_STR,
-            //ErrNo::BFont => EBFONT_STR,
-            ErrNo::NoStr => ENOSTR_STR,
-            ErrNo::NoData => ENODATA_STR,
-            ErrNo::Time => ETIME_STR,
-            ErrNo::NoSR => ENOSR_STR,
-            //ErrNo::NoNet => ENONET_STR,
-            //ErrNo::NoPkg => ENOPKG_STR,
-            //ErrNo::Remote => EREMOTE_STR,
-            ErrNo::NoLink => ENOLINK_STR,
-            //ErrNo::Adv => EADV_STR,
-            //ErrNo::SrMnt => ESRMNT_STR,
-            //ErrNo::Comm => ECOMM_STR,
-            ErrNo::Proto => EPROTO_STR,
-            //ErrNo::MultiHop => EMULTIHOP_STR,
-            //ErrNo::DotDot => EDOTDOT_STR,
-            ErrNo::BadMsg => EBADMSG_STR,
-            ErrNo::Overflow => EOVERFLOW_STR,
-            //ErrNo::NotUniq => ENOTUNIQ_STR,
-            //ErrNo::BadFD => EBADFD_STR,
-            //ErrNo::RemChg => EREMCHG_STR,
-            //ErrNo::LibAcc => ELIBACC_STR,
-            //ErrNo::LibBad => ELIBBAD_STR,
-            //ErrNo::LibScn => ELIBSCN_STR,
-            //ErrNo::LibMax => ELIBMAX_STR,
-            //ErrNo::LibExec => ELIBEXEC_STR,
-            ErrNo::IlSeq => EILSEQ_STR,
-            //ErrNo::Restart => ERESTART_STR,
-            //ErrNo::StrPipe => ESTRPIPE_STR,
-            //ErrNo::Users => EUSERS_STR,
-            ErrNo::NotSock => ENOTSOCK_STR,
-            ErrNo::DestAddrReq => EDESTADDRREQ_STR,
-            ErrNo::MsgSize => EMSGSIZE_STR,
-            ErrNo::ProtoType => EPROTOTYPE_STR,
-            ErrNo::NoProtoOpt => ENOPROTOOPT_STR,
-            ErrNo::ProtoNoSupport => EPROTONOSUPPORT_STR,
-            //ErrNo::SockTNoSupport => ESOCKTNOSUPPORT_STR,
-            ErrNo::OpNotSupp => EOPNOTSUPP_STR,
-            //ErrNo::PFNoSupport => EPFNOSUPPORT_STR,
-            ErrNo::AFNoSupport => EAFNOSUPPORT_STR,
-            ErrNo::AddrInUse => EADDRINUSE_STR,
-            ErrNo::AddrNotAvail => EADDRNOTAVAIL_STR,
-            ErrNo::NetDown => ENETDOWN_STR,
-            ErrNo::NetUnreach => ENETUNREACH_STR,
-            ErrNo::NetReset => ENETRESET_STR,
-            ErrNo::ConnAborted => ECONNABORTED_STR,
-            ErrNo::ConnReset => ECONNRESET_STR,
-            ErrNo::NoBufS => ENOBUFS_STR,
-            ErrNo::IsConn => EISCONN_STR,
-            ErrNo::NotConn => ENOTCONN_STR,
-            //ErrNo::Shutdown => ESHUTDOWN_STR,
-            //ErrNo::TooManyRefs => ETOOMANYREFS_STR,
-            ErrNo::TimedOut => ETIMEDOUT_STR,
-            ErrNo::ConnRefused => ECONNREFUSED_STR,
-            //ErrNo::HostDown => EHOSTDOWN_STR,
-            ErrNo::HostUnreach => EHOSTUNREACH_STR,
-            ErrNo::Already => EALREADY_STR,
-            ErrNo::InProgress => EINPROGRESS_STR,
-            //ErrNo::Stale => ESTALE_STR,
-            //ErrNo::UClean => EUCLEAN_STR,
-            //ErrNo::NotNam => ENOTNAM_STR,
-            //ErrNo::NAvail => ENAVAIL_STR,
-            //ErrNo::IsNam => EISNAM_STR,
-            //ErrNo::RemoteIO => EREMOTEIO_STR,
-            _ => EOTHER_STR,
-        }
+        e.info().name
     }
 }
 
 impl From<&str> for ErrNo {
-    fn from(e: &str) -> Self {
-        match e {
-            EPERM_STR => Self::Perm,
-            ENOENT_STR => Self::NoEnt,
-            ESRCH_STR => Self::Srch,
-            EINTR_STR => Self::Intr,
-            EIO_STR => Self::Io,
-            ENXIO_STR => Self::NxIo,
-            E2BIG_STR => Self::TooBig,
-            ENOEXEC_STR => Self::NoExec,
-            EBADF_STR => Self::BadF,
-            ECHILD_STR => Self::Child,
-            ENOMEM_STR => Self::NoMem,
-            EFAULT_STR => Self::Fault,
-            // ENOTBLK_STR => Self::NotBlk,
-            EBUSY_STR => Self::Busy,
-            EEXIST_STR => Self::Exist,
-            EXDEV_STR => Self::XDev,
-            ENODEV_STR => Self::NoDev,
-            ENOTDIR_STR => Self::NotDir,
-            EISDIR_STR => Self::IsDir,
-            EINVAL_STR => Self::Inval,
-            ENFILE_STR => Self::NFile,
-            EMFILE_STR => Self::MFile,
-            ENOTTY_STR => Self::NotTy,
-            ETXTBSY_STR => Self::TxtBsy,
-            EFBIG_STR => Self::FBig,
-            ENOSPC_STR => Self::NoSpc,
-            ESPIPE_STR => Self::SPipe,
-            EROFS_STR => Self::ROFS,
-            EMLINK_STR => Self::MLink,
-            EPIPE_STR => Self::Pipe,
-            EDOM_STR => Self::Dom,
-            ERANGE_STR => Self::Range,
-            EDEADLK_STR => Self::DeadLk,
-            ENAMETOOLONG_STR => Self::NameTooLong,
-            ENOLCK_STR => Self::NoLck,
-            ENOSYS_STR => Self::NoSys,
-            ENOTEMPTY_STR => Self::NotEmpty,
-            ELOOP_STR => Self::Loop,
-            EWOULDBLOCK_STR => Self::WouldBlock,
-            ENOMSG_STR => Self::NoMsg,
-            EIDRM_STR => Self::IdRm,
-            //ECHRNG_STR => Self::ChRng,
-            //EL2NSYNC_STR => Self::L2NSync,
-            //EL3HLT_STR => Self::L3Hlt,
-            //EL3RST_STR => Self::L3Rst,
-            //ELNRNG_STR => Self::LNRng,
-            //EUNATCH_STR => Self::Unatch,
-            //ENOCSI_STR => Self::NoCSI,
-            //EL2HLT_STR => Self::L2Hlt,
-            //EBADE_STR => Self::BadE,
-            //EBADR_STR => Self::BadR,
-            //EXFULL_STR => Self::XFull,
-            //ENOANO_STR => Self::NoAno,
-            //EBADRQC_STR => Self::BadRqC,
-            //EBADSLT_STR => Self::BadSlt,
-            //EBFONT_STR => Self::BFont,
-            ENOSTR_STR => Self::NoStr,
-            ENODATA_STR => Self::NoData,
-            ETIME_STR => Self::Time,
-            ENOSR_STR => Self::NoSR,
-            //ENONET_STR => Self::NoNet,
-            //ENOPKG_STR => Self::NoPkg,
-            //EREMOTE_STR => Self::Remote,
-            ENOLINK_STR => Self::NoLink,
-            //EADV_STR => Self::Adv,
-            //ESRMNT_STR => Self::SrMnt,
-            //ECOMM_STR => Self::Comm,
-            EPROTO_STR => Self::Proto,
-            //EMULTIHOP_STR => Self::MultiHop,
-            //EDOTDOT_STR => Self::DotDot,
-            EBADMSG_STR => Self::BadMsg,
-            EOVERFLOW_STR => Self::Overflow,
-            //ENOTUNIQ_STR => Self::NotUniq,
-            //EBADFD_STR => Self::BadFD,
-            //EREMCHG_STR => Self::RemChg,
-            //ELIBACC_STR => Self::LibAcc,
-            //ELIBBAD_STR => Self::LibBad,
-            //ELIBSCN_STR => Self::LibScn,
-            //ELIBMAX_STR => Self::LibMax,
-            //ELIBEXEC_STR => Self::LibExec,
-            EILSEQ_STR => Self::IlSeq,
-            //ERESTART_STR => Self::Restart,
-            //ESTRPIPE_STR => Self::StrPipe,
-            //EUSERS_STR => Self::Users,
-            ENOTSOCK_STR => Self::NotSock,
-            EDESTADDRREQ_STR => Self::DestAddrReq,
-            EMSGSIZE_STR => Self::MsgSize,
-            EPROTOTYPE_STR => Self::ProtoType,
-            ENOPROTOOPT_STR => Self::NoProtoOpt,
-            EPROTONOSUPPORT_STR => Self::ProtoNoSupport,
-            //ESOCKTNOSUPPORT_STR => Self::SockTNoSupport,
-            EOPNOTSUPP_STR => Self::OpNotSupp,
-            //EPFNOSUPPORT_STR => Self::PFNoSupport,
-            EAFNOSUPPORT_STR => Self::AFNoSupport,
-            EADDRINUSE_STR => Self::AddrInUse,
-            EADDRNOTAVAIL_STR => Self::AddrNotAvail,
-            ENETDOWN_STR => Self::NetDown,
-            ENETUNREACH_STR => Self::NetUnreach,
-            ENETRESET_STR => Self::NetReset,
-            ECONNABORTED_STR => Self::ConnAborted,
-            ECONNRESET_STR => Self::ConnReset,
-            ENOBUFS_STR => Self::NoBufS,
-            EISCONN_STR => Self::IsConn,
-            ENOTCONN_STR => Self::NotConn,
-            //ESHUTDOWN_STR => Self::Shutdown,
-            //ETOOMANYREFS_STR => Self::TooManyRefs,
-            ETIMEDOUT_STR => Self::TimedOut,
-            ECONNREFUSED_STR => Self::ConnRefused,
-            //EHOSTDOWN_STR => Self::HostDown,
-            EHOSTUNREACH_STR => Self::HostUnreach,
-            EALREADY_STR => Self::Already,
-            EINPROGRESS_STR => Self::InProgress,
-            //ESTALE_STR => Self::Stale,
-            //EUCLEAN_STR => Self::UClean,
-            //ENOTNAM_STR => Self::NotNam,
-            //ENAVAIL_STR => Self::NAvail,
-            //EISNAM_STR => Self::IsNam,
-            //EREMOTEIO_STR => Self::RemoteIO,
-            _ => Self::Other,
+    fn from(s: &str) -> Self {
+        match find_name(s) {
+            Some(info) => info.errno,
+            None => Self::Other,
         }
     }
 }
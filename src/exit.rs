@@ -0,0 +1,227 @@
+//! Process exit codes for OneErr instances, in the style of sysexits.h.
+//!
+//! [OneErr::exit_code] maps an error to one of the `EX_*` codes,
+//! [set_exit_code] overrides the code for a custom `str_kind()`, and
+//! [main] runs a fallible main function, reporting any error to stderr.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::exit::*;
+//!
+//! assert_eq!(EX_NOINPUT, OneErr::from(ErrNo::NoEnt).exit_code());
+//! assert_eq!(EX_SOFTWARE, OneErr::new("MyCustomKind").exit_code());
+//!
+//! set_exit_code("MyConfigKind", EX_CONFIG);
+//! assert_eq!(EX_CONFIG, OneErr::new("MyConfigKind").exit_code());
+//! ```
+//!
+//! ```rust,no_run
+//! fn main() -> std::process::ExitCode {
+//!     one_err::main(|| {
+//!         let _config = one_err::fs::read_to_string("config.json")?;
+//!         Ok(())
+//!     })
+//! }
+//! ```
+
+use crate::*;
+use std::sync::Mutex;
+
+/// Successful termination.
+pub const EX_OK: u8 = 0;
+
+/// The command was used incorrectly.
+pub const EX_USAGE: u8 = 64;
+
+/// The input data was incorrect in some way.
+pub const EX_DATAERR: u8 = 65;
+
+/// An input file did not exist or was not readable.
+pub const EX_NOINPUT: u8 = 66;
+
+/// The user specified did not exist.
+pub const EX_NOUSER: u8 = 67;
+
+/// The host specified did not exist.
+pub const EX_NOHOST: u8 = 68;
+
+/// A service is unavailable.
+pub const EX_UNAVAILABLE: u8 = 69;
+
+/// An internal software error has been detected.
+pub const EX_SOFTWARE: u8 = 70;
+
+/// An operating system error has been detected.
+pub const EX_OSERR: u8 = 71;
+
+/// Some system file does not exist or cannot be opened.
+pub const EX_OSFILE: u8 = 72;
+
+/// A (user specified) output file cannot be created.
+pub const EX_CANTCREAT: u8 = 73;
+
+/// An error occurred while doing I/O on some file.
+pub const EX_IOERR: u8 = 74;
+
+/// Temporary failure, the user is invited to retry.
+pub const EX_TEMPFAIL: u8 = 75;
+
+/// The remote system returned something invalid during a protocol exchange.
+pub const EX_PROTOCOL: u8 = 76;
+
+/// Insufficient permission to perform the operation.
+pub const EX_NOPERM: u8 = 77;
+
+/// Something was found in an unconfigured or misconfigured state.
+pub const EX_CONFIG: u8 = 78;
+
+static EXIT_CODES: Mutex<Vec<(Box<str>, u8)>> = Mutex::new(Vec::new());
+
+/// Map a `str_kind()` to an exit code process wide, replacing any
+/// existing mapping for that kind.
+pub fn set_exit_code<K>(kind: K, code: u8)
+where
+    K: std::fmt::Display,
+{
+    let kind = kind.to_string().into_boxed_str();
+    let mut codes = EXIT_CODES.lock().unwrap_or_else(|e| e.into_inner());
+    codes.retain(|(k, _)| *k != kind);
+    codes.push((kind, code));
+}
+
+impl OneErr {
+    /// The process exit code for this error. Kinds registered with
+    /// [set_exit_code] take precedence, otherwise:
+    ///
+    /// - NotFound -> EX_NOINPUT
+    /// - PermissionDenied -> EX_NOPERM
+    /// - InvalidInput -> EX_USAGE
+    /// - InvalidData / UnexpectedEof -> EX_DATAERR
+    /// - AlreadyExists / StorageFull / ReadOnlyFilesystem -> EX_CANTCREAT
+    /// - unknown hosts (EAI NoName / NoData) -> EX_NOHOST
+    /// - timeouts, refused / reset connections and the like -> EX_TEMPFAIL
+    /// - HostUnreachable / Unsupported -> EX_UNAVAILABLE
+    /// - OutOfMemory -> EX_OSERR
+    /// - BrokenPipe / WriteZero / EIO -> EX_IOERR
+    /// - everything else -> EX_SOFTWARE
+    pub fn exit_code(&self) -> u8 {
+        let kind = self.str_kind();
+        let codes = EXIT_CODES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, code)) = codes.iter().find(|(k, _)| &**k == kind) {
+            return *code;
+        }
+        drop(codes);
+        default_exit_code(self)
+    }
+}
+
+fn default_exit_code(e: &OneErr) -> u8 {
+    use std::io::ErrorKind::*;
+    match e.eai() {
+        EaiErr::NoName | EaiErr::NoData => return EX_NOHOST,
+        eai if eai.is_transient() => return EX_TEMPFAIL,
+        _ => (),
+    }
+    match e.io_kind() {
+        NotFound => EX_NOINPUT,
+        PermissionDenied => EX_NOPERM,
+        InvalidInput => EX_USAGE,
+        InvalidData | UnexpectedEof => EX_DATAERR,
+        AlreadyExists | StorageFull | ReadOnlyFilesystem | QuotaExceeded => {
+            EX_CANTCREAT
+        }
+        TimedOut | WouldBlock | Interrupted | ConnectionRefused
+        | ConnectionReset | ConnectionAborted | NetworkDown
+        | NetworkUnreachable | ResourceBusy => EX_TEMPFAIL,
+        HostUnreachable | Unsupported => EX_UNAVAILABLE,
+        OutOfMemory => EX_OSERR,
+        BrokenPipe | WriteZero => EX_IOERR,
+        _ => match e.errno() {
+            ErrNo::Io => EX_IOERR,
+            errno if errno.is_transient() => EX_TEMPFAIL,
+            _ => EX_SOFTWARE,
+        },
+    }
+}
+
+/// A human-readable, multi-line report of the error, e.g.
+///
+/// ```text
+/// NotFound: No such file or directory
+///   op: read
+///   path: config.json
+/// ```
+///
+/// Errors without a message use the description of their errno, if any.
+pub fn report(e: &OneErr) -> String {
+    let mut out = e.str_kind().to_string();
+    let message = match (e.get_message(), e.errno()) {
+        (Some(message), _) => Some(message),
+        (None, ErrNo::Other) => None,
+        (None, errno) => Some(errno.description()),
+    };
+    if let Some(message) = message {
+        out.push_str(": ");
+        out.push_str(message);
+    }
+    if let Some(fields) = &*e.priv_as_inner().0 {
+        for (name, value) in fields.iter() {
+            match &**name {
+                ERROR | OS | MESSAGE => continue,
+                _ => (),
+            }
+            out.push_str("\n  ");
+            out.push_str(name);
+            out.push_str(": ");
+            match value {
+                Value::Null => out.push_str("null"),
+                Value::Bool(b) => out.push_str(&b.to_string()),
+                Value::I64(i) => out.push_str(&i.to_string()),
+                Value::U64(u) => out.push_str(&u.to_string()),
+                Value::F64(f) => out.push_str(&f.to_string()),
+                Value::String(s) => out.push_str(s),
+            }
+        }
+    }
+    out
+}
+
+/// Run a fallible main function. Errors are reported to stderr
+/// and converted to their [OneErr::exit_code].
+pub fn main<F>(f: F) -> std::process::ExitCode
+where
+    F: FnOnce() -> Result<(), OneErr>,
+{
+    match f() {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", report(&e));
+            e.exit_code().into()
+        }
+    }
+}
+
+/// A `std::process::Termination` wrapper, for returning from main
+/// with the [OneErr::exit_code] of an error.
+///
+/// ```rust,no_run
+/// use one_err::*;
+///
+/// fn main() -> exit::Exit {
+///     Err(OneErr::from(ErrNo::NoEnt)).into()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Exit(pub Result<(), OneErr>);
+
+impl<E: Into<OneErr>> From<Result<(), E>> for Exit {
+    fn from(r: Result<(), E>) -> Self {
+        Self(r.map_err(Into::into))
+    }
+}
+
+impl std::process::Termination for Exit {
+    fn report(self) -> std::process::ExitCode {
+        main(|| self.0)
+    }
+}
@@ -0,0 +1,318 @@
+//! C FFI for passing OneErr values across a C / C++ boundary.
+//!
+//! Enabled with the `ffi` feature. The matching C declarations are in
+//! `include/one_err.h`.
+//!
+//! A `one_err_t*` is an opaque, owned handle that must be released with
+//! `one_err_free`. Strings returned by `one_err_kind` and
+//! `one_err_message` are borrowed from the handle and live as long as it
+//! does. Strings returned by `one_err_get_field_str` and `one_err_to_json`
+//! are owned by the caller and must be released with
+//! `one_err_string_free`. All functions accept NULL handles.
+
+#![allow(unsafe_code)]
+
+use crate::*;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+
+/// Opaque C handle to a OneErr, `one_err_t` in C.
+pub struct OneErrHandle {
+    err: OneErr,
+    kind: CString,
+    message: Option<CString>,
+}
+
+impl OneErrHandle {
+    fn new(err: OneErr) -> *mut Self {
+        let kind = to_c_string(err.str_kind());
+        let message = err.get_message().map(to_c_string);
+        Box::into_raw(Box::new(Self { err, kind, message }))
+    }
+
+    /// The wrapped OneErr.
+    pub fn as_one_err(&self) -> &OneErr {
+        &self.err
+    }
+}
+
+/// Hand ownership of a OneErr to C.
+/// The returned handle must be released with `one_err_free`.
+pub fn into_handle(err: OneErr) -> *mut OneErrHandle {
+    OneErrHandle::new(err)
+}
+
+// interior nul bytes cannot be represented, truncate at the first one
+fn to_c_string(s: &str) -> CString {
+    let s = s.split('\0').next().unwrap_or_default();
+    CString::new(s).unwrap_or_default()
+}
+
+/// # Safety
+/// `s` must be NULL or a valid nul terminated string.
+unsafe fn from_c_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+unsafe fn get_err<'a>(e: *const OneErrHandle) -> Option<&'a OneErr> {
+    e.as_ref().map(|h| &h.err)
+}
+
+/// Set the C `errno` of the current thread from a OneErr,
+/// returning the value that was set. Errors without an errno
+/// (`ErrNo::Other`) leave `errno` unchanged and return 0.
+pub fn set_errno(e: &OneErr) -> c_int {
+    let errno = match e.errno() {
+        ErrNo::Other => return 0,
+        errno => i32::from(errno),
+    };
+    // safety: the errno location is always valid for the current thread
+    unsafe {
+        if let Some(loc) = errno_location() {
+            *loc = errno;
+        }
+    }
+    errno
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "emscripten",
+    target_os = "redox",
+    target_os = "dragonfly",
+    target_os = "fuchsia"
+))]
+unsafe fn errno_location() -> Option<*mut c_int> {
+    Some(libc::__errno_location())
+}
+
+#[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+unsafe fn errno_location() -> Option<*mut c_int> {
+    Some(libc::__errno())
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+unsafe fn errno_location() -> Option<*mut c_int> {
+    Some(libc::__error())
+}
+
+#[cfg(windows)]
+unsafe fn errno_location() -> Option<*mut c_int> {
+    extern "C" {
+        fn _errno() -> *mut c_int;
+    }
+    Some(_errno())
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "emscripten",
+    target_os = "redox",
+    target_os = "dragonfly",
+    target_os = "fuchsia",
+    target_os = "android",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    windows
+)))]
+unsafe fn errno_location() -> Option<*mut c_int> {
+    None
+}
+
+/// Create a new error with a `str_kind()` and an optional message.
+/// Returns NULL if `kind` is NULL or not utf8.
+///
+/// # Safety
+/// `kind` and `message` must be NULL or valid nul terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_new(
+    kind: *const c_char,
+    message: *const c_char,
+) -> *mut OneErrHandle {
+    let kind = match from_c_str(kind) {
+        Some(kind) => kind,
+        None => return std::ptr::null_mut(),
+    };
+    let err = match from_c_str(message) {
+        Some(message) => OneErr::with_message(kind, message),
+        None => OneErr::new(kind),
+    };
+    OneErrHandle::new(err)
+}
+
+/// Release an error handle.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module,
+/// it must not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_free(e: *mut OneErrHandle) {
+    if !e.is_null() {
+        drop(Box::from_raw(e));
+    }
+}
+
+/// Release a string returned by `one_err_get_field_str` or
+/// `one_err_to_json`.
+///
+/// # Safety
+/// `s` must be NULL or a string returned by one of those functions,
+/// it must not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// The `str_kind()` of the error, borrowed from the handle.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_kind(e: *const OneErrHandle) -> *const c_char {
+    match e.as_ref() {
+        Some(h) => h.kind.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// The errno of the error on the current platform,
+/// or -1 if there is none.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_errno(e: *const OneErrHandle) -> c_int {
+    match get_err(e) {
+        Some(err) => i32::from(err.errno()),
+        None => -1,
+    }
+}
+
+/// Set the C `errno` from the error, returning the value that was set.
+/// Errors without an errno leave `errno` unchanged and return 0.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_set_errno(e: *const OneErrHandle) -> c_int {
+    match get_err(e) {
+        Some(err) => set_errno(err),
+        None => -1,
+    }
+}
+
+/// The message of the error borrowed from the handle, or NULL.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_message(
+    e: *const OneErrHandle,
+) -> *const c_char {
+    match e.as_ref().and_then(|h| h.message.as_ref()) {
+        Some(m) => m.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// A string field of the error, or NULL if there is no such string field.
+/// Free the result with `one_err_string_free`.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module,
+/// `name` must be NULL or a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_get_field_str(
+    e: *const OneErrHandle,
+    name: *const c_char,
+) -> *mut c_char {
+    match (get_err(e), from_c_str(name)) {
+        (Some(err), Some(name)) => match err.get_field::<_, &str>(name) {
+            Some(s) => to_c_string(s).into_raw(),
+            None => std::ptr::null_mut(),
+        },
+        _ => std::ptr::null_mut(),
+    }
+}
+
+macro_rules! get_field {
+    ($(#[$doc:meta] $name:ident: $t:ty,)*) => {$(
+        #[$doc]
+        /// Writes the value to `out` and returns 1 if the field exists
+        /// with that type, otherwise returns 0.
+        ///
+        /// # Safety
+        /// `e` must be NULL or a live handle from this module,
+        /// `name` must be NULL or a valid nul terminated string,
+        /// `out` must be NULL or valid for writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $name(
+            e: *const OneErrHandle,
+            name: *const c_char,
+            out: *mut $t,
+        ) -> c_int {
+            if out.is_null() {
+                return 0;
+            }
+            match (get_err(e), from_c_str(name)) {
+                (Some(err), Some(name)) => match err.get_field::<_, $t>(name) {
+                    Some(v) => {
+                        *out = v;
+                        1
+                    }
+                    None => 0,
+                },
+                _ => 0,
+            }
+        }
+    )*};
+}
+
+get_field! {
+    /// Get a bool field of the error.
+    one_err_get_field_bool: bool,
+    /// Get a signed integer field of the error.
+    one_err_get_field_i64: i64,
+    /// Get an unsigned integer field of the error.
+    one_err_get_field_u64: u64,
+    /// Get a floating point field of the error.
+    one_err_get_field_f64: f64,
+}
+
+/// Encode the error as json. Free the result with `one_err_string_free`.
+///
+/// # Safety
+/// `e` must be NULL or a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_to_json(
+    e: *const OneErrHandle,
+) -> *mut c_char {
+    match get_err(e) {
+        Some(err) => to_c_string(&err.to_string()).into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Decode an error from json, returns NULL if the json is invalid.
+///
+/// # Safety
+/// `json` must be NULL or a valid nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn one_err_from_json(
+    json: *const c_char,
+) -> *mut OneErrHandle {
+    match from_c_str(json).map(str::parse::<OneErr>) {
+        Some(Ok(err)) => OneErrHandle::new(err),
+        _ => std::ptr::null_mut(),
+    }
+}
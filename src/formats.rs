@@ -0,0 +1,54 @@
+// MessagePack and CBOR encodings of OneErr, behind the `rmp-serde` and
+// `ciborium` features. Well-known kinds are encoded as integer tags.
+
+use crate::*;
+
+impl OneErr {
+    /// Encode this instance as MessagePack.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let err = OneErr::with_message("NotFound", "no such thing");
+    /// let dec = OneErr::from_msgpack(&err.to_msgpack()).unwrap();
+    /// assert_eq!(err, dec);
+    /// ```
+    #[cfg(feature = "rmp-serde")]
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        rmp_serde::to_vec(&TaggedKind(self))
+            .expect("encoding to a Vec cannot fail")
+    }
+
+    /// Decode an instance from MessagePack.
+    #[cfg(feature = "rmp-serde")]
+    pub fn from_msgpack(data: &[u8]) -> Result<Self, OneErr> {
+        rmp_serde::from_slice(data)
+            .map(|FromTaggedKind(err)| err)
+            .map_err(OneErr::from)
+    }
+
+    /// Encode this instance as CBOR.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let err = OneErr::with_message("NotFound", "no such thing");
+    /// let dec = OneErr::from_cbor(&err.to_cbor()).unwrap();
+    /// assert_eq!(err, dec);
+    /// ```
+    #[cfg(feature = "ciborium")]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        ciborium::into_writer(&TaggedKind(self), &mut out)
+            .expect("encoding to a Vec cannot fail");
+        out
+    }
+
+    /// Decode an instance from CBOR.
+    #[cfg(feature = "ciborium")]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, OneErr> {
+        ciborium::from_reader(data)
+            .map(|FromTaggedKind(err)| err)
+            .map_err(OneErr::from)
+    }
+}
@@ -0,0 +1,267 @@
+//! Wrappers around `std::fs` that attach the path and operation to errors.
+//!
+//! Each function mirrors its `std::fs` counterpart and returns a
+//! `std::io::Result`, so they can be used as drop-in replacements.
+//! On failure the `std::io::Error` is a OneErr carrying the errno of the
+//! failure, plus `op`, `path`, and (for two-path operations) `to` fields.
+//!
+//! ```rust
+//! use one_err::*;
+//!
+//! let err = one_err::fs::read("/this/does/not/exist").unwrap_err();
+//! let err = OneErr::from(err);
+//!
+//! assert_eq!(ErrNo::NoEnt, err.errno());
+//! assert_eq!(Some("read"), err.get_field(one_err::fs::OP_FIELD));
+//! assert_eq!(
+//!     Some("/this/does/not/exist"),
+//!     err.get_field(one_err::fs::PATH_FIELD),
+//! );
+//! ```
+
+use crate::*;
+use std::path::Path;
+
+/// Field naming the fs operation that failed.
+pub const OP_FIELD: &str = "op";
+
+/// Field holding the path the fs operation was acting on.
+pub const PATH_FIELD: &str = "path";
+
+/// Field holding the destination path of two-path fs operations.
+pub const TO_FIELD: &str = "to";
+
+#[track_caller]
+fn wrap<T>(
+    op: &'static str,
+    path: &Path,
+    res: std::io::Result<T>,
+) -> std::io::Result<T> {
+    match res {
+        Ok(t) => Ok(t),
+        Err(e) => {
+            let mut err = OneErr::from(e);
+            err.set_field(OP_FIELD, op)
+                .set_field(PATH_FIELD, path.to_string_lossy().as_ref());
+            Err(err.into())
+        }
+    }
+}
+
+#[track_caller]
+fn wrap2<T>(
+    op: &'static str,
+    from: &Path,
+    to: &Path,
+    res: std::io::Result<T>,
+) -> std::io::Result<T> {
+    match res {
+        Ok(t) => Ok(t),
+        Err(e) => {
+            let mut err = OneErr::from(e);
+            err.set_field(OP_FIELD, op)
+                .set_field(PATH_FIELD, from.to_string_lossy().as_ref())
+                .set_field(TO_FIELD, to.to_string_lossy().as_ref());
+            Err(err.into())
+        }
+    }
+}
+
+/// Wraps `std::fs::read`.
+#[track_caller]
+pub fn read<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<u8>> {
+    let path = path.as_ref();
+    wrap("read", path, std::fs::read(path))
+}
+
+/// Wraps `std::fs::read_to_string`.
+#[track_caller]
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> std::io::Result<String> {
+    let path = path.as_ref();
+    wrap("read_to_string", path, std::fs::read_to_string(path))
+}
+
+/// Wraps `std::fs::write`.
+#[track_caller]
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(
+    path: P,
+    contents: C,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("write", path, std::fs::write(path, contents))
+}
+
+/// Wraps `std::fs::File::open`.
+#[track_caller]
+pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<std::fs::File> {
+    let path = path.as_ref();
+    wrap("open", path, std::fs::File::open(path))
+}
+
+/// Wraps `std::fs::File::create`.
+#[track_caller]
+pub fn create<P: AsRef<Path>>(path: P) -> std::io::Result<std::fs::File> {
+    let path = path.as_ref();
+    wrap("create", path, std::fs::File::create(path))
+}
+
+/// Wraps `std::fs::OpenOptions::open`.
+#[track_caller]
+pub fn open_with<P: AsRef<Path>>(
+    options: &std::fs::OpenOptions,
+    path: P,
+) -> std::io::Result<std::fs::File> {
+    let path = path.as_ref();
+    wrap("open", path, options.open(path))
+}
+
+/// Wraps `std::fs::copy`.
+#[track_caller]
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(
+    from: P,
+    to: Q,
+) -> std::io::Result<u64> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    wrap2("copy", from, to, std::fs::copy(from, to))
+}
+
+/// Wraps `std::fs::rename`.
+#[track_caller]
+pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(
+    from: P,
+    to: Q,
+) -> std::io::Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    wrap2("rename", from, to, std::fs::rename(from, to))
+}
+
+/// Wraps `std::fs::hard_link`.
+#[track_caller]
+pub fn hard_link<P: AsRef<Path>, Q: AsRef<Path>>(
+    original: P,
+    link: Q,
+) -> std::io::Result<()> {
+    let (original, link) = (original.as_ref(), link.as_ref());
+    wrap2(
+        "hard_link",
+        original,
+        link,
+        std::fs::hard_link(original, link),
+    )
+}
+
+/// Wraps `std::fs::create_dir`.
+#[track_caller]
+pub fn create_dir<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("create_dir", path, std::fs::create_dir(path))
+}
+
+/// Wraps `std::fs::create_dir_all`.
+#[track_caller]
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("create_dir_all", path, std::fs::create_dir_all(path))
+}
+
+/// Wraps `std::fs::remove_dir`.
+#[track_caller]
+pub fn remove_dir<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("remove_dir", path, std::fs::remove_dir(path))
+}
+
+/// Wraps `std::fs::remove_dir_all`.
+#[track_caller]
+pub fn remove_dir_all<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("remove_dir_all", path, std::fs::remove_dir_all(path))
+}
+
+/// Wraps `std::fs::remove_file`.
+#[track_caller]
+pub fn remove_file<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap("remove_file", path, std::fs::remove_file(path))
+}
+
+/// Wraps `std::fs::metadata`.
+#[track_caller]
+pub fn metadata<P: AsRef<Path>>(path: P) -> std::io::Result<std::fs::Metadata> {
+    let path = path.as_ref();
+    wrap("metadata", path, std::fs::metadata(path))
+}
+
+/// Wraps `std::fs::symlink_metadata`.
+#[track_caller]
+pub fn symlink_metadata<P: AsRef<Path>>(
+    path: P,
+) -> std::io::Result<std::fs::Metadata> {
+    let path = path.as_ref();
+    wrap("symlink_metadata", path, std::fs::symlink_metadata(path))
+}
+
+/// Wraps `std::fs::read_link`.
+#[track_caller]
+pub fn read_link<P: AsRef<Path>>(
+    path: P,
+) -> std::io::Result<std::path::PathBuf> {
+    let path = path.as_ref();
+    wrap("read_link", path, std::fs::read_link(path))
+}
+
+/// Wraps `std::fs::canonicalize`.
+#[track_caller]
+pub fn canonicalize<P: AsRef<Path>>(
+    path: P,
+) -> std::io::Result<std::path::PathBuf> {
+    let path = path.as_ref();
+    wrap("canonicalize", path, std::fs::canonicalize(path))
+}
+
+/// Wraps `std::fs::set_permissions`.
+#[track_caller]
+pub fn set_permissions<P: AsRef<Path>>(
+    path: P,
+    perm: std::fs::Permissions,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    wrap(
+        "set_permissions",
+        path,
+        std::fs::set_permissions(path, perm),
+    )
+}
+
+/// Wraps `std::fs::read_dir`.
+#[track_caller]
+pub fn read_dir<P: AsRef<Path>>(path: P) -> std::io::Result<ReadDir> {
+    let path = path.as_ref();
+    match std::fs::read_dir(path) {
+        Ok(inner) => Ok(ReadDir {
+            inner,
+            path: path.to_path_buf(),
+        }),
+        Err(e) => wrap("read_dir", path, Err(e)),
+    }
+}
+
+/// Wraps `std::fs::ReadDir`, attaching the directory path to
+/// errors encountered while iterating.
+#[derive(Debug)]
+pub struct ReadDir {
+    inner: std::fs::ReadDir,
+    path: std::path::PathBuf,
+}
+
+impl Iterator for ReadDir {
+    type Item = std::io::Result<std::fs::DirEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = &self.path;
+        match self.inner.next()? {
+            Ok(entry) => Some(Ok(entry)),
+            Err(e) => Some(wrap("read_dir", path, Err(e))),
+        }
+    }
+}
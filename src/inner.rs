@@ -13,7 +13,7 @@ impl OneErrInner {
         T: Into<Value>,
     {
         if self.0.is_none() {
-            self.0 = Box::new(Some(TopMap::new()));
+            *self.0 = Some(TopMap::new());
         }
         self.0.as_mut().as_mut().unwrap().insert(name, t.into());
     }
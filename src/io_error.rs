@@ -59,3 +59,60 @@ pub const UNSUPPORTED_STR: &str = "Unsupported";
 
 /// The const str 'OutOfMemory'.
 pub const OUT_OF_MEMORY_STR: &str = "OutOfMemory";
+
+/// The const str 'ArgumentListTooLong'.
+pub const ARGUMENT_LIST_TOO_LONG_STR: &str = "ArgumentListTooLong";
+
+/// The const str 'ResourceBusy'.
+pub const RESOURCE_BUSY_STR: &str = "ResourceBusy";
+
+/// The const str 'CrossesDevices'.
+pub const CROSSES_DEVICES_STR: &str = "CrossesDevices";
+
+/// The const str 'NotADirectory'.
+pub const NOT_A_DIRECTORY_STR: &str = "NotADirectory";
+
+/// The const str 'IsADirectory'.
+pub const IS_A_DIRECTORY_STR: &str = "IsADirectory";
+
+/// The const str 'ExecutableFileBusy'.
+pub const EXECUTABLE_FILE_BUSY_STR: &str = "ExecutableFileBusy";
+
+/// The const str 'FileTooLarge'.
+pub const FILE_TOO_LARGE_STR: &str = "FileTooLarge";
+
+/// The const str 'StorageFull'.
+pub const STORAGE_FULL_STR: &str = "StorageFull";
+
+/// The const str 'NotSeekable'.
+pub const NOT_SEEKABLE_STR: &str = "NotSeekable";
+
+/// The const str 'ReadOnlyFilesystem'.
+pub const READ_ONLY_FILESYSTEM_STR: &str = "ReadOnlyFilesystem";
+
+/// The const str 'TooManyLinks'.
+pub const TOO_MANY_LINKS_STR: &str = "TooManyLinks";
+
+/// The const str 'Deadlock'.
+pub const DEADLOCK_STR: &str = "Deadlock";
+
+/// The const str 'InvalidFilename'.
+pub const INVALID_FILENAME_STR: &str = "InvalidFilename";
+
+/// The const str 'DirectoryNotEmpty'.
+pub const DIRECTORY_NOT_EMPTY_STR: &str = "DirectoryNotEmpty";
+
+/// The const str 'NetworkDown'.
+pub const NETWORK_DOWN_STR: &str = "NetworkDown";
+
+/// The const str 'NetworkUnreachable'.
+pub const NETWORK_UNREACHABLE_STR: &str = "NetworkUnreachable";
+
+/// The const str 'HostUnreachable'.
+pub const HOST_UNREACHABLE_STR: &str = "HostUnreachable";
+
+/// The const str 'QuotaExceeded'.
+pub const QUOTA_EXCEEDED_STR: &str = "QuotaExceeded";
+
+/// The const str 'StaleNetworkFileHandle'.
+pub const STALE_NETWORK_FILE_HANDLE_STR: &str = "StaleNetworkFileHandle";
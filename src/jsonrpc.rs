@@ -0,0 +1,277 @@
+//! JSON-RPC 2.0 error object encoding for OneErr.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::jsonrpc::*;
+//!
+//! let mut err = OneErr::with_message("InvalidInput", "bad param");
+//! err.set_field("param", "count");
+//!
+//! let rpc = JsonRpcError::from(&err);
+//! assert_eq!(INVALID_PARAMS, rpc.code);
+//! assert_eq!("bad param", rpc.message);
+//!
+//! let dec = OneErr::from(rpc);
+//! assert_eq!(err, dec);
+//! ```
+
+use crate::*;
+
+/// JSON-RPC 2.0 "Parse error" code.
+pub const PARSE_ERROR: i64 = -32700;
+
+/// JSON-RPC 2.0 "Invalid Request" code.
+pub const INVALID_REQUEST: i64 = -32600;
+
+/// JSON-RPC 2.0 "Method not found" code.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC 2.0 "Invalid params" code.
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// JSON-RPC 2.0 "Internal error" code.
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Field name used to record the json-rpc code when decoding an error
+/// object that did not carry OneErr data.
+pub const CODE_FIELD: &str = "code";
+
+/// Field name used to record json-rpc data that was not a OneErr object.
+pub const DATA_FIELD: &str = "data";
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRpcError {
+    /// The json-rpc error code.
+    pub code: i64,
+
+    /// A short description of the error.
+    pub message: String,
+
+    /// Additional data, for OneErr sources this is the OneErr object.
+    pub data: Option<serde_json::Value>,
+}
+
+impl std::fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_display(f, self)
+    }
+}
+
+impl serde::Serialize for JsonRpcError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let count = if self.data.is_some() { 3 } else { 2 };
+        let mut map = serializer.serialize_map(Some(count))?;
+        map.serialize_entry("code", &self.code)?;
+        map.serialize_entry("message", &self.message)?;
+        if let Some(data) = &self.data {
+            map.serialize_entry("data", data)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for JsonRpcError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct V;
+        impl<'de> serde::de::Visitor<'de> for V {
+            type Value = JsonRpcError;
+
+            fn expecting(
+                &self,
+                f: &mut std::fmt::Formatter<'_>,
+            ) -> std::fmt::Result {
+                f.write_str("JSON-RPC error object")
+            }
+
+            fn visit_map<A>(
+                self,
+                mut access: A,
+            ) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut code = None;
+                let mut message = None;
+                let mut data = None;
+                while let Some(k) = access.next_key::<Box<str>>()? {
+                    match &*k {
+                        "code" => code = Some(access.next_value()?),
+                        "message" => message = Some(access.next_value()?),
+                        "data" => data = Some(access.next_value()?),
+                        _ => {
+                            access.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                let code = code.ok_or_else(|| {
+                    serde::de::Error::custom("required 'code' field")
+                })?;
+                let message = message.ok_or_else(|| {
+                    serde::de::Error::custom("required 'message' field")
+                })?;
+                Ok(JsonRpcError {
+                    code,
+                    message,
+                    data,
+                })
+            }
+        }
+        deserializer.deserialize_map(V)
+    }
+}
+
+impl std::str::FromStr for JsonRpcError {
+    type Err = OneErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(OneErr::from)
+    }
+}
+
+impl From<&OneErr> for JsonRpcError {
+    fn from(e: &OneErr) -> Self {
+        JsonRpcCodes::default().encode(e)
+    }
+}
+
+impl From<OneErr> for JsonRpcError {
+    fn from(e: OneErr) -> Self {
+        (&e).into()
+    }
+}
+
+impl From<&JsonRpcError> for OneErr {
+    #[track_caller]
+    fn from(e: &JsonRpcError) -> Self {
+        JsonRpcCodes::default().decode(e)
+    }
+}
+
+impl From<JsonRpcError> for OneErr {
+    #[track_caller]
+    fn from(e: JsonRpcError) -> Self {
+        (&e).into()
+    }
+}
+
+/// A configurable `str_kind()` to json-rpc code table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRpcCodes {
+    codes: Vec<(Box<str>, i64)>,
+    default_code: i64,
+}
+
+impl Default for JsonRpcCodes {
+    /// The standard table: InvalidInput -> INVALID_PARAMS,
+    /// InvalidData -> PARSE_ERROR, NotFound -> METHOD_NOT_FOUND,
+    /// everything else -> INTERNAL_ERROR.
+    fn default() -> Self {
+        let mut out = Self::new();
+        out.set_code(crate::io_error::INVALID_INPUT_STR, INVALID_PARAMS)
+            .set_code(crate::io_error::INVALID_DATA_STR, PARSE_ERROR)
+            .set_code(crate::io_error::NOT_FOUND_STR, METHOD_NOT_FOUND);
+        out
+    }
+}
+
+impl JsonRpcCodes {
+    /// An empty table, mapping everything to INTERNAL_ERROR.
+    pub fn new() -> Self {
+        Self {
+            codes: Vec::new(),
+            default_code: INTERNAL_ERROR,
+        }
+    }
+
+    /// Map a `str_kind()` to a json-rpc code, replacing any existing
+    /// mapping for that kind.
+    pub fn set_code<K>(&mut self, kind: K, code: i64) -> &mut Self
+    where
+        K: std::fmt::Display,
+    {
+        let kind = kind.to_string().into_boxed_str();
+        self.codes.retain(|(k, _)| *k != kind);
+        self.codes.push((kind, code));
+        self
+    }
+
+    /// Set the code used for kinds not in this table.
+    pub fn set_default_code(&mut self, code: i64) -> &mut Self {
+        self.default_code = code;
+        self
+    }
+
+    /// Get the json-rpc code for a OneErr instance.
+    pub fn code_for(&self, e: &OneErr) -> i64 {
+        let kind = e.str_kind();
+        for (k, c) in self.codes.iter() {
+            if &**k == kind {
+                return *c;
+            }
+        }
+        self.default_code
+    }
+
+    /// Get the first `str_kind()` mapped to a json-rpc code, if any.
+    pub fn kind_for(&self, code: i64) -> Option<&str> {
+        for (k, c) in self.codes.iter() {
+            if *c == code {
+                return Some(k);
+            }
+        }
+        None
+    }
+
+    /// Encode a OneErr as a json-rpc error object.
+    /// The data member carries the full OneErr object.
+    pub fn encode(&self, e: &OneErr) -> JsonRpcError {
+        let message = match e.get_message() {
+            Some(m) => m.to_string(),
+            None => e.str_kind().to_string(),
+        };
+        JsonRpcError {
+            code: self.code_for(e),
+            message,
+            data: serde_json::to_value(e).ok(),
+        }
+    }
+
+    /// Decode a json-rpc error object received from a peer.
+    /// If the data member is a OneErr object, that is returned directly.
+    /// Otherwise the kind is looked up from the code, and the code
+    /// and data are recorded as fields.
+    #[track_caller]
+    pub fn decode(&self, e: &JsonRpcError) -> OneErr {
+        if let Some(data) = &e.data {
+            if let Ok(err) = serde_json::from_value::<OneErr>(data.clone()) {
+                return err;
+            }
+        }
+
+        let mut err = match self.kind_for(e.code) {
+            Some(kind) => OneErr::with_message(kind, &e.message),
+            None => {
+                OneErr::with_message(crate::io_error::OTHER_STR, &e.message)
+            }
+        };
+        err.set_field(CODE_FIELD, e.code);
+        match &e.data {
+            None => (),
+            Some(serde_json::Value::String(s)) => {
+                err.set_field(DATA_FIELD, s.as_str());
+            }
+            Some(data) => {
+                err.set_field(DATA_FIELD, data.to_string());
+            }
+        }
+        err
+    }
+}
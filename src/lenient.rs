@@ -0,0 +1,157 @@
+// Lenient parsing of OneErr instances from arbitrary error text,
+// see `OneErr::parse_lenient`.
+
+use crate::*;
+
+impl OneErr {
+    /// Parse an error from json, or failing that, from free-form error
+    /// text such as a line of a program's stderr. This never fails,
+    /// text that is not recognized becomes the message of an
+    /// `Other` kind error. Recognized, in order:
+    ///
+    /// - the json object form, as accepted by `FromStr`
+    /// - logfmt, as accepted by [OneErr::from_logfmt]
+    /// - a bare kind: an errno, io kind, EAI or windows error name
+    ///   (case-insensitive, io kinds may be spaced, e.g.
+    ///   `connection refused`), an errno code, or an errno description
+    /// - `KIND: message`, where KIND is a bare kind as above
+    /// - `message (os error N)`, as displayed by std::io::Error
+    /// - `anything: DESCRIPTION`, e.g. `ls: cannot access 'x': No such
+    ///   file or directory`, keeping the whole text as the message
+    ///
+    /// An optional leading `error:` is ignored.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let err = OneErr::parse_lenient("ENOENT: no such file");
+    /// assert_eq!(ErrNo::NoEnt, err.errno());
+    /// assert_eq!(Some("no such file"), err.get_message());
+    ///
+    /// let err = OneErr::parse_lenient("connection refused");
+    /// assert_eq!(std::io::ErrorKind::ConnectionRefused, err.io_kind());
+    ///
+    /// let err = OneErr::parse_lenient("something odd happened");
+    /// assert_eq!("Other", err.str_kind());
+    /// assert_eq!(Some("something odd happened"), err.get_message());
+    /// ```
+    pub fn parse_lenient(s: &str) -> Self {
+        let s = s.trim();
+
+        if s.starts_with('{') {
+            if let Ok(err) = s.parse() {
+                return err;
+            }
+        }
+
+        if let Ok(err) = OneErr::from_logfmt(s) {
+            return err;
+        }
+
+        let s = strip_prefix_ignore_case(s, "error:").unwrap_or(s);
+
+        if let Some(kind) = lookup_kind(s) {
+            return OneErr::new(kind);
+        }
+
+        if let Some((head, rest)) = s.split_once(':') {
+            if let Some(kind) = lookup_kind(head) {
+                return OneErr::with_message(kind, rest.trim());
+            }
+        }
+
+        if let Some((message, code)) = split_os_error(s) {
+            let errno = ErrNo::from(code);
+            if errno != ErrNo::Other {
+                return OneErr::with_message(errno, message);
+            }
+        }
+
+        if let Some((_, tail)) = s.rsplit_once(':') {
+            if let Some(kind) = lookup_description(tail.trim()) {
+                return OneErr::with_message(kind, s);
+            }
+        }
+
+        if s.is_empty() {
+            OneErr::new(crate::io_error::OTHER_STR)
+        } else {
+            OneErr::with_message(crate::io_error::OTHER_STR, s)
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(s[prefix.len()..].trim_start())
+    } else {
+        None
+    }
+}
+
+// "No such file or directory (os error 2)"
+fn split_os_error(s: &str) -> Option<(&str, i32)> {
+    let rest = s.strip_suffix(')')?;
+    let (message, code) = rest.rsplit_once("(os error ")?;
+    Some((message.trim_end(), code.parse().ok()?))
+}
+
+// lowercase with spaces, underscores and dashes removed
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The canonical kind string for a bare kind, if recognized.
+fn lookup_kind(s: &str) -> Option<String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    if let Ok(code) = s.parse::<i32>() {
+        return match ErrNo::from(code) {
+            ErrNo::Other => None,
+            errno => Some(<&'static str>::from(errno).to_string()),
+        };
+    }
+
+    let upper = s.to_ascii_uppercase();
+    if ErrNo::from(upper.as_str()) != ErrNo::Other
+        || EaiErr::from(upper.as_str()) != EaiErr::Other
+        || WinErr::from(upper.as_str()) != WinErr::Other
+    {
+        return Some(upper);
+    }
+
+    let norm = normalize(s);
+    if let Some((_, kind)) =
+        IO_KIND_TAGS.iter().find(|(_, k)| normalize(k) == norm)
+    {
+        return Some(kind.to_string());
+    }
+
+    lookup_description(s)
+}
+
+/// The errno or io kind string for an error description,
+/// e.g. "No such file or directory" or "entity not found".
+fn lookup_description(s: &str) -> Option<String> {
+    if let Some(errno) =
+        ErrNo::all().find(|e| e.description().eq_ignore_ascii_case(s))
+    {
+        return Some(<&'static str>::from(errno).to_string());
+    }
+    IO_KIND_TAGS
+        .iter()
+        .find(|(_, k)| {
+            let (kind, _) = parse_err_str(k);
+            std::io::Error::from(kind)
+                .to_string()
+                .eq_ignore_ascii_case(s)
+        })
+        .map(|(_, k)| k.to_string())
+}
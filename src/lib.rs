@@ -123,17 +123,71 @@
 mod errno_;
 pub use errno_::*;
 
+mod eai_;
+pub use eai_::*;
+
+mod platform_;
+pub use platform_::*;
+
+mod winerr_;
+pub use winerr_::*;
+
+pub mod compact;
+
+pub mod exit;
+pub use exit::main;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod fs;
+
 pub mod io_error;
 
+pub mod jsonrpc;
+
+pub mod limits;
+
+pub mod ndjson;
+
+pub mod net;
+
+pub mod panic;
+pub use panic::{catch_unwind, catch_unwind_async};
+
+pub mod parse_error;
+
+pub mod process;
+
+#[cfg(feature = "prost")]
+pub mod proto;
+
+pub mod serde_error;
+
+pub mod std_error;
+
 mod value;
 pub use value::*;
 
 mod inner;
 use inner::*;
 
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+mod formats;
+
+mod lenient;
+
+mod logfmt;
+
+mod tag;
+use tag::*;
+
 mod util;
 use util::*;
 
+mod sys;
+pub use sys::*;
+
 mod one_err;
 pub use crate::one_err::*;
 
@@ -0,0 +1,267 @@
+//! Resource limits for deserializing OneErr instances from untrusted input.
+//!
+//! The plain `FromStr` and `Deserialize` impls accept any number of fields
+//! of any size. For input from untrusted peers, parse with
+//! [OneErr::from_str_with_limits], or, for other serde formats, use a
+//! `&Limits` as a `serde::de::DeserializeSeed`. Keys and string values are
+//! checked before they are copied into the result, though the
+//! deserializer may already have buffered them, e.g. serde_json unescaping
+//! a string into its scratch buffer.
+//!
+//! Exceeding a limit is an InvalidData error. From
+//! [OneErr::from_str_with_limits] it also carries a [LIMIT_FIELD] naming
+//! the limit, e.g. [MAX_FIELDS].
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::limits::*;
+//!
+//! let limits = Limits {
+//!     max_fields: 2,
+//!     ..Default::default()
+//! };
+//!
+//! let ok = r#"{"error":"NotFound","message":"gone"}"#;
+//! assert!(OneErr::from_str_with_limits(ok, &limits).is_ok());
+//!
+//! let bad = r#"{"error":"NotFound","message":"gone","path":"/tmp"}"#;
+//! let err = OneErr::from_str_with_limits(bad, &limits).unwrap_err();
+//! assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+//! assert_eq!(Some(MAX_FIELDS), err.get_field(LIMIT_FIELD));
+//! ```
+
+use crate::*;
+use serde::de::{DeserializeSeed, Error};
+use std::cell::Cell;
+
+/// Field naming the limit that was exceeded.
+pub const LIMIT_FIELD: &str = "limit";
+
+/// [LIMIT_FIELD] value for [Limits::max_fields].
+pub const MAX_FIELDS: &str = "max_fields";
+
+/// [LIMIT_FIELD] value for [Limits::max_key_len].
+pub const MAX_KEY_LEN: &str = "max_key_len";
+
+/// [LIMIT_FIELD] value for [Limits::max_value_len].
+pub const MAX_VALUE_LEN: &str = "max_value_len";
+
+/// [LIMIT_FIELD] value for [Limits::max_total_len].
+pub const MAX_TOTAL_LEN: &str = "max_total_len";
+
+/// Bounds on the size of a OneErr being deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of entries, including `error` and `message`.
+    /// Defaults to 128.
+    pub max_fields: usize,
+
+    /// The maximum length of a field name in bytes. Defaults to 256.
+    pub max_key_len: usize,
+
+    /// The maximum length of a string value in bytes. Defaults to 64 KiB.
+    pub max_value_len: usize,
+
+    /// The maximum length of the whole payload in bytes, checked by
+    /// [OneErr::from_str_with_limits]. Defaults to 1 MiB.
+    pub max_total_len: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_fields: 128,
+            max_key_len: 256,
+            max_value_len: 64 * 1024,
+            max_total_len: 1024 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    /// No limits at all, the behavior of the plain `Deserialize` impl.
+    pub fn unlimited() -> Self {
+        Self {
+            max_fields: usize::MAX,
+            max_key_len: usize::MAX,
+            max_value_len: usize::MAX,
+            max_total_len: usize::MAX,
+        }
+    }
+}
+
+impl OneErr {
+    /// Parse the json form, as `FromStr` does, within the given limits.
+    pub fn from_str_with_limits(
+        s: &str,
+        limits: &Limits,
+    ) -> Result<Self, OneErr> {
+        if s.len() > limits.max_total_len {
+            let mut err = OneErr::with_message(
+                crate::io_error::INVALID_DATA_STR,
+                format!(
+                    "payload of {} bytes exceeds {} of {}",
+                    s.len(),
+                    MAX_TOTAL_LEN,
+                    limits.max_total_len
+                ),
+            );
+            err.set_field(LIMIT_FIELD, MAX_TOTAL_LEN);
+            return Err(err);
+        }
+
+        let exceeded = Cell::new(None);
+        let mut de = serde_json::Deserializer::from_str(s);
+        Seed {
+            limits,
+            exceeded: &exceeded,
+        }
+        .deserialize(&mut de)
+        .and_then(|err| de.end().map(|_| err))
+        .map_err(|e| {
+            let mut err = crate::parse_error::json_error(
+                e,
+                std::io::ErrorKind::InvalidData,
+            );
+            if let Some(limit) = exceeded.get() {
+                err.set_field(LIMIT_FIELD, limit);
+            }
+            err
+        })
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for &Limits {
+    type Value = OneErr;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<OneErr, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let exceeded = Cell::new(None);
+        Seed {
+            limits: self,
+            exceeded: &exceeded,
+        }
+        .deserialize(deserializer)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Seed<'a> {
+    limits: &'a Limits,
+    exceeded: &'a Cell<Option<&'static str>>,
+}
+
+impl Seed<'_> {
+    fn exceeded<E: Error>(&self, limit: &'static str, what: String) -> E {
+        self.exceeded.set(Some(limit));
+        E::custom(what)
+    }
+
+    fn check_value_len(&self, len: usize) -> Result<(), String> {
+        if len > self.limits.max_value_len {
+            self.exceeded.set(Some(MAX_VALUE_LEN));
+            return Err(format!(
+                "value of {} bytes exceeds {} of {}",
+                len, MAX_VALUE_LEN, self.limits.max_value_len
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Seed<'_> {
+    type Value = OneErr;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<OneErr, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let top_map = deserializer.deserialize_map(self)?;
+        OneErr::priv_from_top_map(top_map).map_err(D::Error::custom)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for Seed<'_> {
+    type Value = TopMap;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OneErr Map")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<TopMap, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut out_map = TopMap::new();
+        while let Some(k) = access.next_key_seed(Key(self))? {
+            if out_map.len() >= self.limits.max_fields
+                && !out_map.contains_key(&k)
+            {
+                return Err(self.exceeded(
+                    MAX_FIELDS,
+                    format!(
+                        "more than {} of {} fields",
+                        MAX_FIELDS, self.limits.max_fields
+                    ),
+                ));
+            }
+            let v = access.next_value_seed(Field(self))?;
+            out_map.insert(k, v);
+        }
+        Ok(out_map)
+    }
+}
+
+struct Key<'a>(Seed<'a>);
+
+impl<'de> DeserializeSeed<'de> for Key<'_> {
+    type Value = Box<str>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Box<str>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl serde::de::Visitor<'_> for Key<'_> {
+    type Value = Box<str>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a field name")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Box<str>, E> {
+        if v.len() > self.0.limits.max_key_len {
+            return Err(self.0.exceeded(
+                MAX_KEY_LEN,
+                format!(
+                    "field name of {} bytes exceeds {} of {}",
+                    v.len(),
+                    MAX_KEY_LEN,
+                    self.0.limits.max_key_len
+                ),
+            ));
+        }
+        Ok(v.into())
+    }
+}
+
+struct Field<'a>(Seed<'a>);
+
+impl<'de> DeserializeSeed<'de> for Field<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let seed = self.0;
+        deserializer.deserialize_any(crate::value::ValueVisitor(Some(
+            move |len| seed.check_value_len(len),
+        )))
+    }
+}
@@ -0,0 +1,248 @@
+// logfmt (key=value) rendering and parsing of OneErr instances.
+
+use crate::parse_error::*;
+use crate::*;
+use std::fmt::Write;
+
+impl OneErr {
+    /// Render this instance as a logfmt line, e.g.
+    /// `error=NotFound message="no such file" path=/tmp/x retries=3`.
+    ///
+    /// The `error` kind comes first, followed by the fields in the
+    /// same order as the json form. Strings are quoted when they contain
+    /// whitespace, quotes, `=` or `\`, are empty, or would otherwise read
+    /// back as a null, bool or number. Inside quotes, `"` and `\` are
+    /// backslash escaped, as are control characters (`\n`, `\r`, `\t`,
+    /// or `\u00XX`). Nulls render as `null`, non-finite floats as `null`
+    /// as in the json form. Characters that cannot appear in a key
+    /// (whitespace, `=` and `"`) are replaced by `_`.
+    ///
+    /// Scalar values round-trip through [OneErr::from_logfmt], with the
+    /// same caveat as json: positive i64 values read back as u64.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let mut err = OneErr::with_message(ErrNo::NoEnt, "no \"such\" file");
+    /// err.set_field("path", "/tmp/x").set_field("retries", 3_u64);
+    ///
+    /// let line = err.to_logfmt();
+    /// assert_eq!(
+    ///     r#"error=NotFound message="no \"such\" file" path=/tmp/x retries=3"#,
+    ///     line,
+    /// );
+    /// assert_eq!(err, OneErr::from_logfmt(&line).unwrap());
+    /// ```
+    pub fn to_logfmt(&self) -> String {
+        let mut out = String::new();
+        put_key(&mut out, ERROR);
+        put_str(&mut out, self.str_kind(), false);
+        if let Some(f) = &*self.priv_as_inner().0 {
+            for (n, v) in f.iter() {
+                match &**n {
+                    ERROR | OS => continue,
+                    _ => (),
+                }
+                out.push(' ');
+                put_key(&mut out, n);
+                put_value(&mut out, v);
+            }
+        }
+        out
+    }
+
+    /// Parse a logfmt line, as rendered by [OneErr::to_logfmt].
+    /// Unquoted values are typed as json would type them, an `error`
+    /// value is always the kind string. Without an `error` key the kind
+    /// is `Other`. Fails with an InvalidData error carrying the byte
+    /// `offset` if the line is not entirely `key=value` pairs.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// let err = OneErr::from_logfmt("error=ENOENT path=/tmp/x n=-3").unwrap();
+    /// assert_eq!(ErrNo::NoEnt, err.errno());
+    /// assert_eq!(Some("/tmp/x"), err.get_field("path"));
+    /// assert_eq!(Some(-3), err.get_field::<_, i64>("n"));
+    ///
+    /// assert!(OneErr::from_logfmt("no pairs here").is_err());
+    /// ```
+    pub fn from_logfmt(s: &str) -> Result<Self, OneErr> {
+        let mut top_map = TopMap::new();
+        let mut pos = skip_ws(s, 0);
+        if pos == s.len() {
+            return Err(syntax("no key=value pairs", pos));
+        }
+        while pos < s.len() {
+            let rest = &s[pos..];
+            let key_len = rest
+                .find(|c: char| c == '=' || c == '"' || c.is_whitespace())
+                .unwrap_or(rest.len());
+            if key_len == 0 || !rest[key_len..].starts_with('=') {
+                return Err(syntax("expected key=value", pos));
+            }
+            let key = &rest[..key_len];
+            pos += key_len + 1;
+
+            let rest = &s[pos..];
+            let value = if let Some(quoted) = rest.strip_prefix('"') {
+                let (value, len) = unquote(quoted)
+                    .map_err(|(msg, at)| syntax(msg, pos + 1 + at))?;
+                pos += 1 + len;
+                Value::String(value.into_boxed_str())
+            } else {
+                let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let raw = &rest[..len];
+                if raw.contains('"') {
+                    return Err(syntax("unexpected quote", pos));
+                }
+                pos += len;
+                if key == ERROR {
+                    raw.into()
+                } else {
+                    typed(raw)
+                }
+            };
+            if pos < s.len() && !s[pos..].starts_with(char::is_whitespace) {
+                return Err(syntax("expected whitespace", pos));
+            }
+            pos = skip_ws(s, pos);
+            top_map.insert(key.into(), value);
+        }
+        if !top_map.contains_key(ERROR) {
+            top_map.insert(ERROR.into(), crate::io_error::OTHER_STR.into());
+        }
+        OneErr::priv_from_top_map(top_map).map_err(|msg| syntax(msg, 0))
+    }
+}
+
+fn syntax(message: &str, offset: usize) -> OneErr {
+    let mut err =
+        OneErr::with_message(crate::io_error::INVALID_DATA_STR, message);
+    err.set_field(FORMAT_FIELD, LOGFMT)
+        .set_field(CATEGORY_FIELD, SYNTAX)
+        .set_field(OFFSET_FIELD, offset as u64);
+    err
+}
+
+fn skip_ws(s: &str, pos: usize) -> usize {
+    s.len() - s[pos..].trim_start().len()
+}
+
+fn put_key(out: &mut String, key: &str) {
+    if key.is_empty() {
+        out.push('_');
+    }
+    for c in key.chars() {
+        if c == '=' || c == '"' || c.is_whitespace() {
+            out.push('_');
+        } else {
+            out.push(c);
+        }
+    }
+    out.push('=');
+}
+
+fn put_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::I64(i) => out.push_str(&i.to_string()),
+        Value::U64(u) => out.push_str(&u.to_string()),
+        // debug formatting always includes a '.' or exponent
+        Value::F64(f) if f.is_finite() => out.push_str(&format!("{:?}", f)),
+        Value::F64(_) => out.push_str("null"),
+        Value::String(s) => put_str(out, s, true),
+    }
+}
+
+fn put_str(out: &mut String, s: &str, typed_value: bool) {
+    let plain = !s.is_empty()
+        && !s.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || c == '"'
+                || c == '='
+                || c == '\\'
+        })
+        && (!typed_value || matches!(typed(s), Value::String(_)));
+    if plain {
+        out.push_str(s);
+        return;
+    }
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Unquote the body of a quoted value, returning the value and the
+/// length consumed including the closing quote, or an error message
+/// and offset.
+fn unquote(s: &str) -> Result<(String, usize), (&'static str, usize)> {
+    let mut out = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, i + 1)),
+            '\\' => match chars.next() {
+                Some((_, '"')) => out.push('"'),
+                Some((_, '\\')) => out.push('\\'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 'r')) => out.push('\r'),
+                Some((_, 't')) => out.push('\t'),
+                Some((_, 'u')) => {
+                    let hex =
+                        s.get(i + 2..i + 6).ok_or(("bad \\u escape", i))?;
+                    let c = u32::from_str_radix(hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or(("bad \\u escape", i))?;
+                    out.push(c);
+                    for _ in 0..4 {
+                        chars.next();
+                    }
+                }
+                Some(_) => return Err(("unknown escape", i)),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err(("unterminated quoted value", s.len()))
+}
+
+/// Type an unquoted value the way json would.
+fn typed(raw: &str) -> Value {
+    match raw {
+        "null" => return Value::Null,
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => (),
+    }
+    if raw.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+        if let Ok(u) = raw.parse::<u64>() {
+            return Value::U64(u);
+        }
+        if let Ok(i) = raw.parse::<i64>() {
+            return Value::I64(i);
+        }
+        if let Ok(f) = raw.parse::<f64>() {
+            if f.is_finite() {
+                return Value::F64(f);
+            }
+        }
+    }
+    raw.into()
+}
@@ -0,0 +1,441 @@
+//! Newline delimited json (NDJSON) error logs.
+//!
+//! [Writer] appends one OneErr per line, in its `Display` form, optionally
+//! adding [TIMESTAMP_FIELD] and [SEQUENCE_FIELD] fields, with size-based
+//! rotation and a [SyncPolicy]. [Reader] parses them back with `FromStr`,
+//! skipping corrupt or oversized lines and dropping a truncated final
+//! line, all of which are recorded in its [Report].
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::ndjson::*;
+//!
+//! let mut w = Writer::new(Vec::new());
+//! w.set_sequence(true);
+//! w.write(&OneErr::from(ErrNo::NoEnt)).unwrap();
+//! w.write(&OneErr::new("MyKind")).unwrap();
+//!
+//! let mut data = w.into_inner();
+//! data.extend_from_slice(b"not json\n{\"error\":\"Trunc");
+//!
+//! let mut r = Reader::new(&data[..]);
+//! let errs = r.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+//! assert_eq!(2, errs.len());
+//! assert_eq!(Some(1), errs[1].get_field::<_, u64>(SEQUENCE_FIELD));
+//! assert_eq!(1, r.report().skipped);
+//! assert!(r.report().truncated);
+//! ```
+
+use crate::*;
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Field holding the unix time, in milliseconds, a record was written at.
+pub const TIMESTAMP_FIELD: &str = "timestamp";
+
+/// Field holding the 0-based sequence number of a record in a log.
+pub const SEQUENCE_FIELD: &str = "sequence";
+
+/// The default longest line, in bytes, a [Reader] will parse.
+pub const DEFAULT_MAX_LINE_LEN: usize = 1024 * 1024;
+
+/// The number of most recent skipped line errors a [Report] keeps.
+pub const MAX_SKIPPED_ERRORS: usize = 16;
+
+/// When a [Writer] makes written records durable.
+/// For `Writer<File>` this is `File::sync_data`, for other writers it is
+/// `Write::flush`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncPolicy {
+    /// Never, leave it to the writer and the OS.
+    #[default]
+    Never,
+
+    /// After every record.
+    Always,
+
+    /// After every N records.
+    Every(u64),
+}
+
+type Rotate<W> = Box<dyn FnMut() -> std::io::Result<W> + Send>;
+
+/// Appends OneErr instances to a writer, one json object per line.
+pub struct Writer<W: Write> {
+    writer: W,
+    timestamp: bool,
+    sequence: bool,
+    next_seq: u64,
+    written: u64,
+    max_bytes: u64,
+    rotate: Option<Rotate<W>>,
+    sync_policy: SyncPolicy,
+    sync: fn(&mut W) -> std::io::Result<()>,
+    unsynced: u64,
+}
+
+impl<W: Write> std::fmt::Debug for Writer<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Writer")
+            .field("timestamp", &self.timestamp)
+            .field("sequence", &self.sequence)
+            .field("next_seq", &self.next_seq)
+            .field("written", &self.written)
+            .field("max_bytes", &self.max_bytes)
+            .field("sync_policy", &self.sync_policy)
+            .finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Append records to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            timestamp: false,
+            sequence: false,
+            next_seq: 0,
+            written: 0,
+            max_bytes: u64::MAX,
+            rotate: None,
+            sync_policy: SyncPolicy::Never,
+            sync: W::flush,
+            unsynced: 0,
+        }
+    }
+
+    /// Add a [TIMESTAMP_FIELD] to each record. Defaults to false.
+    pub fn set_timestamp(&mut self, timestamp: bool) -> &mut Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Add a [SEQUENCE_FIELD] to each record. Defaults to false.
+    pub fn set_sequence(&mut self, sequence: bool) -> &mut Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Set when records are made durable. Defaults to [SyncPolicy::Never].
+    pub fn set_sync_policy(&mut self, sync_policy: SyncPolicy) -> &mut Self {
+        self.sync_policy = sync_policy;
+        self
+    }
+
+    /// Once writing a record would grow the current writer past
+    /// `max_bytes`, sync it and continue on the writer returned by
+    /// `rotate`. A record larger than `max_bytes` still gets written,
+    /// alone. Also see [Writer::set_rotation] for files.
+    pub fn set_rotation_with<F>(
+        &mut self,
+        max_bytes: u64,
+        rotate: F,
+    ) -> &mut Self
+    where
+        F: FnMut() -> std::io::Result<W> + Send + 'static,
+    {
+        self.max_bytes = max_bytes;
+        self.rotate = Some(Box::new(rotate));
+        self
+    }
+
+    /// Append a record.
+    pub fn write(&mut self, err: &OneErr) -> std::io::Result<()> {
+        let mut line = if self.timestamp || self.sequence {
+            let mut err = err.clone();
+            if self.timestamp {
+                err.set_field(TIMESTAMP_FIELD, unix_millis());
+            }
+            if self.sequence {
+                err.set_field(SEQUENCE_FIELD, self.next_seq);
+            }
+            err.to_string()
+        } else {
+            err.to_string()
+        };
+        line.push('\n');
+        let len = line.len() as u64;
+
+        if self.written > 0 && self.written.saturating_add(len) > self.max_bytes
+        {
+            if let Some(rotate) = &mut self.rotate {
+                (self.sync)(&mut self.writer)?;
+                self.writer = rotate()?;
+                self.written = 0;
+                self.unsynced = 0;
+            }
+        }
+
+        self.writer.write_all(line.as_bytes())?;
+        self.written += len;
+        self.next_seq += 1;
+        self.unsynced += 1;
+
+        let sync = match self.sync_policy {
+            SyncPolicy::Never => false,
+            SyncPolicy::Always => true,
+            SyncPolicy::Every(n) => self.unsynced >= n,
+        };
+        if sync {
+            (self.sync)(&mut self.writer)?;
+            self.unsynced = 0;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl Writer<std::fs::File> {
+    /// Append records to the file at `path`, creating it if needed.
+    /// Sequence numbers continue after the last record in the file
+    /// that has one. Syncing uses `File::sync_data`.
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let mut file = open_append(path.as_ref())?;
+        let written = crate::fs::metadata(path.as_ref())?.len();
+        let next_seq = last_sequence(&mut file, written)?.map_or(0, |s| s + 1);
+        let mut w = Self::new(file);
+        w.written = written;
+        w.next_seq = next_seq;
+        w.sync = |f| f.sync_data();
+        Ok(w)
+    }
+
+    /// Rotate the file at `path` once it would grow past `max_bytes`,
+    /// shifting `path` to `path.1`, `path.1` to `path.2`, and so on,
+    /// deleting the oldest beyond `keep` rotated files.
+    pub fn set_rotation<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        max_bytes: u64,
+        keep: usize,
+    ) -> &mut Self {
+        let path = path.as_ref().to_path_buf();
+        self.set_rotation_with(max_bytes, move || {
+            rotate_files(&path, keep)?;
+            open_append(&path)
+        })
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<std::fs::File> {
+    crate::fs::open_with(
+        std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true),
+        path,
+    )
+}
+
+/// The [SEQUENCE_FIELD] of the last record that has one, looking at
+/// most one [DEFAULT_MAX_LINE_LEN] line back from the end of the file.
+fn last_sequence(
+    file: &mut std::fs::File,
+    len: u64,
+) -> std::io::Result<Option<u64>> {
+    use std::io::Seek;
+    let tail_len = len.min(DEFAULT_MAX_LINE_LEN as u64 + 1);
+    file.seek(std::io::SeekFrom::Start(len - tail_len))?;
+    let mut tail = Vec::new();
+    file.take(tail_len).read_to_end(&mut tail)?;
+    Ok(tail
+        .split(|b| *b == b'\n')
+        .rev()
+        .filter_map(|line| std::str::from_utf8(line).ok()?.parse().ok())
+        .find_map(|err: OneErr| err.get_field(SEQUENCE_FIELD)))
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut p = path.as_os_str().to_os_string();
+    p.push(format!(".{}", n));
+    p.into()
+}
+
+fn rotate_files(path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return crate::fs::remove_file(path);
+    }
+    for n in (1..keep).rev() {
+        let from = rotated(path, n);
+        if from.exists() {
+            crate::fs::rename(&from, rotated(path, n + 1))?;
+        }
+    }
+    crate::fs::rename(path, rotated(path, 1))
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// What a [Reader] skipped.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The number of records read.
+    pub records: u64,
+
+    /// The number of corrupt or oversized lines skipped.
+    pub skipped: u64,
+
+    /// The 1-based line number and error of the most recently skipped
+    /// lines, oldest first, at most [MAX_SKIPPED_ERRORS] of them.
+    pub skipped_errors: std::collections::VecDeque<(u64, OneErr)>,
+
+    /// Whether an incomplete final line, e.g. from a crash mid-write,
+    /// was dropped.
+    pub truncated: bool,
+}
+
+/// Reads OneErr instances from a reader, one json object per line.
+/// Blank lines are ignored. Iteration ends after an io error.
+#[derive(Debug)]
+pub struct Reader<R: BufRead> {
+    reader: R,
+    line: Vec<u8>,
+    line_no: u64,
+    max_line_len: usize,
+    report: Report,
+    done: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Read records from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_no: 0,
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+            report: Report::default(),
+            done: false,
+        }
+    }
+
+    /// Skip lines longer than `max_line_len` bytes, excluding the newline,
+    /// without buffering them. Defaults to [DEFAULT_MAX_LINE_LEN].
+    pub fn set_max_line_len(&mut self, max_line_len: usize) -> &mut Self {
+        self.max_line_len = max_line_len;
+        self
+    }
+
+    /// What has been read and skipped so far.
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn skip(&mut self, err: OneErr) {
+        self.report.skipped += 1;
+        if self.report.skipped_errors.len() == MAX_SKIPPED_ERRORS {
+            self.report.skipped_errors.pop_front();
+        }
+        self.report.skipped_errors.push_back((self.line_no, err));
+    }
+
+    /// Discard the rest of the current line, including its newline.
+    fn discard_line(&mut self) -> std::io::Result<()> {
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                    continue
+                }
+                Err(e) => return Err(e),
+            };
+            if buf.is_empty() {
+                return Ok(());
+            }
+            match buf.iter().position(|b| *b == b'\n') {
+                Some(i) => {
+                    self.reader.consume(i + 1);
+                    return Ok(());
+                }
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<OneErr, OneErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            // one byte past the limit, for the newline
+            let limit = self.max_line_len as u64 + 1;
+            let res = (&mut self.reader)
+                .take(limit)
+                .read_until(b'\n', &mut self.line);
+            match res {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line_no += 1;
+                    let complete = self.line.ends_with(b"\n");
+                    if !complete && self.line.len() > self.max_line_len {
+                        if let Err(e) = self.discard_line() {
+                            self.done = true;
+                            return Some(Err(e.into()));
+                        }
+                        self.skip(OneErr::with_message(
+                            crate::io_error::INVALID_DATA_STR,
+                            format!("line exceeds {} bytes", self.max_line_len),
+                        ));
+                        continue;
+                    }
+                    let res = std::str::from_utf8(&self.line)
+                        .map_err(OneErr::from)
+                        .and_then(|line| {
+                            let line = line.trim();
+                            if line.is_empty() {
+                                Ok(None)
+                            } else {
+                                line.parse::<OneErr>().map(Some)
+                            }
+                        });
+                    match res {
+                        Ok(None) => (),
+                        Ok(Some(err)) => {
+                            self.report.records += 1;
+                            return Some(Ok(err));
+                        }
+                        Err(_) if !complete => {
+                            self.report.truncated = true;
+                            self.done = true;
+                        }
+                        Err(e) => self.skip(e),
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,199 @@
+//! Wrappers around `std::net` sockets that attach addresses to errors.
+//!
+//! Each function returns a `std::io::Result`, so they can be used as
+//! drop-in replacements. On failure the `std::io::Error` is a OneErr
+//! carrying the errno of the failure, plus `op` and, where known,
+//! `peer` and `local` address fields. Functions that resolve an address
+//! and try each result return the error of the last one, with all the
+//! socket addresses tried listed in an `addr` field.
+//!
+//! ```rust
+//! use one_err::*;
+//!
+//! // find a loopback port nobody is listening on
+//! let port = std::net::TcpListener::bind("127.0.0.1:0")
+//!     .unwrap()
+//!     .local_addr()
+//!     .unwrap()
+//!     .port();
+//! let peer = format!("127.0.0.1:{}", port);
+//!
+//! let err = one_err::net::tcp_connect(&peer).unwrap_err();
+//! let err = OneErr::from(err);
+//!
+//! assert_eq!(std::io::ErrorKind::ConnectionRefused, err.io_kind());
+//! assert_eq!(Some("connect"), err.get_field(one_err::net::OP_FIELD));
+//! assert_eq!(Some(peer.as_str()), err.get_field(one_err::net::PEER_FIELD));
+//! ```
+
+use crate::*;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+
+/// Field naming the network operation that failed.
+pub const OP_FIELD: &str = "op";
+
+/// Field holding the remote address of the failed operation.
+pub const PEER_FIELD: &str = "peer";
+
+/// Field holding the local address of the failed operation.
+pub const LOCAL_FIELD: &str = "local";
+
+/// Field holding the comma separated socket addresses that were tried,
+/// when they all failed.
+pub const ADDR_FIELD: &str = "addr";
+
+#[track_caller]
+fn wrap<T>(
+    op: &'static str,
+    peer: Option<&dyn std::fmt::Display>,
+    local: Option<&dyn std::fmt::Display>,
+    res: std::io::Result<T>,
+) -> std::io::Result<T> {
+    let e = match res {
+        Ok(t) => return Ok(t),
+        Err(e) => e,
+    };
+    let mut err = OneErr::from(e);
+    err.set_field(OP_FIELD, op);
+    if let Some(peer) = peer {
+        err.set_field(PEER_FIELD, peer.to_string());
+    }
+    if let Some(local) = local {
+        err.set_field(LOCAL_FIELD, local.to_string());
+    }
+    Err(err.into())
+}
+
+#[track_caller]
+fn resolve<A: ToSocketAddrs>(addr: A) -> std::io::Result<Vec<SocketAddr>> {
+    let addrs: Vec<SocketAddr> =
+        wrap("resolve", None, None, addr.to_socket_addrs())?.collect();
+    if addrs.is_empty() {
+        return wrap(
+            "resolve",
+            None,
+            None,
+            Err(OneErr::with_message(
+                crate::io_error::INVALID_INPUT_STR,
+                "could not resolve to any addresses",
+            )
+            .into()),
+        );
+    }
+    Ok(addrs)
+}
+
+/// Try `f` against each resolved address, returning the first success,
+/// or the last error annotated with the address that produced it.
+#[track_caller]
+fn each_addr<A, T, F>(
+    op: &'static str,
+    is_peer: bool,
+    addr: A,
+    mut f: F,
+) -> std::io::Result<T>
+where
+    A: ToSocketAddrs,
+    F: FnMut(SocketAddr) -> std::io::Result<T>,
+{
+    let addrs = resolve(addr)?;
+    let mut last_err = None;
+    for addr in addrs.iter().copied() {
+        let res = if is_peer {
+            wrap(op, Some(&addr), None, f(addr))
+        } else {
+            wrap(op, None, Some(&addr), f(addr))
+        };
+        match res {
+            Ok(t) => return Ok(t),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    let mut err = OneErr::from(last_err.unwrap());
+    let addrs = addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    err.set_field(ADDR_FIELD, addrs.join(","));
+    Err(err.into())
+}
+
+/// Wraps `std::net::TcpStream::connect`.
+#[track_caller]
+pub fn tcp_connect<A: ToSocketAddrs>(addr: A) -> std::io::Result<TcpStream> {
+    each_addr("connect", true, addr, TcpStream::connect)
+}
+
+/// Wraps `std::net::TcpStream::connect_timeout`.
+#[track_caller]
+pub fn tcp_connect_timeout(
+    addr: &SocketAddr,
+    timeout: std::time::Duration,
+) -> std::io::Result<TcpStream> {
+    wrap(
+        "connect",
+        Some(addr),
+        None,
+        TcpStream::connect_timeout(addr, timeout),
+    )
+}
+
+/// Wraps `std::net::TcpListener::bind`.
+#[track_caller]
+pub fn tcp_bind<A: ToSocketAddrs>(addr: A) -> std::io::Result<TcpListener> {
+    each_addr("bind", false, addr, TcpListener::bind)
+}
+
+/// Wraps `std::net::UdpSocket::bind`.
+#[track_caller]
+pub fn udp_bind<A: ToSocketAddrs>(addr: A) -> std::io::Result<UdpSocket> {
+    each_addr("bind", false, addr, UdpSocket::bind)
+}
+
+/// Wraps `std::net::UdpSocket::send_to`.
+#[track_caller]
+pub fn udp_send_to<A: ToSocketAddrs>(
+    socket: &UdpSocket,
+    buf: &[u8],
+    addr: A,
+) -> std::io::Result<usize> {
+    let local = socket.local_addr().ok();
+    let addr = resolve(addr)?[0];
+    wrap(
+        "send_to",
+        Some(&addr),
+        local.as_ref().map(|l| l as &dyn std::fmt::Display),
+        socket.send_to(buf, addr),
+    )
+}
+
+/// Wraps `std::net::UdpSocket::recv_from`.
+#[track_caller]
+pub fn udp_recv_from(
+    socket: &UdpSocket,
+    buf: &mut [u8],
+) -> std::io::Result<(usize, SocketAddr)> {
+    let res = socket.recv_from(buf);
+    let local = match &res {
+        Ok(_) => None,
+        Err(_) => socket.local_addr().ok(),
+    };
+    wrap(
+        "recv_from",
+        None,
+        local.as_ref().map(|l| l as &dyn std::fmt::Display),
+        res,
+    )
+}
+
+/// Wraps `std::os::unix::net::UnixStream::connect`.
+#[cfg(unix)]
+#[track_caller]
+pub fn unix_connect<P: AsRef<std::path::Path>>(
+    path: P,
+) -> std::io::Result<std::os::unix::net::UnixStream> {
+    let path = path.as_ref();
+    wrap(
+        "connect",
+        Some(&path.display()),
+        None,
+        std::os::unix::net::UnixStream::connect(path),
+    )
+}
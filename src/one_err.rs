@@ -1,10 +1,14 @@
 use super::*;
 
-const ERROR: &str = "error";
-const OS: &str = "os";
-const SOURCE: &str = "source";
-const BACKTRACE: &str = "backtrace";
-const MESSAGE: &str = "message";
+pub(crate) const ERROR: &str = "error";
+pub(crate) const OS: &str = "os";
+pub(crate) const SOURCE: &str = "source";
+pub(crate) const BACKTRACE: &str = "backtrace";
+pub(crate) const MESSAGE: &str = "message";
+pub(crate) const LOCATION: &str = "location";
+
+static CAPTURE_LOCATION: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
 
 /// OneErr to rule them all. See crate docs for usage.
 pub struct OneErr(std::io::Error);
@@ -46,35 +50,35 @@ impl PartialEq for OneErr {
 impl Eq for OneErr {}
 
 impl From<std::io::ErrorKind> for OneErr {
+    #[track_caller]
     fn from(k: std::io::ErrorKind) -> Self {
-        Self(std::io::Error::new(k, OneErrInner::new()))
+        Self(std::io::Error::new(k, OneErrInner::new())).priv_with_location()
     }
 }
 
 impl From<i32> for OneErr {
+    #[track_caller]
     fn from(e: i32) -> Self {
-        ErrNo::from(e).into()
+        Self::from(ErrNo::from(e))
     }
 }
 
 impl From<ErrNo> for OneErr {
+    #[track_caller]
     fn from(e: ErrNo) -> Self {
         let k: std::io::ErrorKind = (&e).into();
 
         let mut inner = OneErrInner::new();
-        if let std::io::ErrorKind::Other = k {
-            if let ErrNo::Other = e {
-                /* pass */
-            } else {
-                inner.set_field(OS.into(), i32::from(&e) as i64);
-            }
+        if keep_errno(k, e) {
+            inner.set_field(OS.into(), i32::from(&e) as i64);
         }
 
-        Self(std::io::Error::new(k, inner))
+        Self(std::io::Error::new(k, inner)).priv_with_location()
     }
 }
 
 impl From<std::io::Error> for OneErr {
+    #[track_caller]
     fn from(e: std::io::Error) -> Self {
         // we have to be careful about this one...
         // we need the inner to be a OneErrInner.
@@ -88,37 +92,51 @@ impl From<std::io::Error> for OneErr {
         }
 
         // if there is an os errno, use that
+        #[cfg(not(windows))]
         if let Some(e) = e.raw_os_error() {
-            return e.into();
+            return Self::from(e);
+        }
+
+        // on windows the raw os error is a Win32 code, not an errno
+        #[cfg(windows)]
+        if let Some(code) = e.raw_os_error() {
+            let errno = WinErr::from(code).errno();
+            if errno != ErrNo::Other {
+                return Self::from(errno);
+            }
         }
 
         // otherwise, just go off the io::ErrorKind
         let message = format!("{}", e);
         let mut inner = OneErrInner::new();
         inner.set_field(MESSAGE.into(), message);
-        Self(std::io::Error::new(e.kind(), inner))
+        Self(std::io::Error::new(e.kind(), inner)).priv_with_location()
     }
 }
 
 impl From<()> for OneErr {
+    #[track_caller]
     fn from(_: ()) -> Self {
-        std::io::ErrorKind::Other.into()
+        Self::from(std::io::ErrorKind::Other)
     }
 }
 
 impl From<String> for OneErr {
+    #[track_caller]
     fn from(s: String) -> Self {
-        s.as_str().into()
+        Self::from(s.as_str())
     }
 }
 
 impl From<&String> for OneErr {
+    #[track_caller]
     fn from(s: &String) -> Self {
-        s.as_str().into()
+        Self::from(s.as_str())
     }
 }
 
 impl From<&str> for OneErr {
+    #[track_caller]
     fn from(s: &str) -> Self {
         OneErr::new(s)
     }
@@ -152,48 +170,47 @@ impl AsRef<std::io::Error> for OneErr {
 
 impl serde::Serialize for OneErr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.priv_serialize(serializer, false)
+    }
+}
+
+/// Serializes well-known kinds as integer tags rather than strings,
+/// for the compact binary encodings.
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+pub(crate) struct TaggedKind<'a>(pub(crate) &'a OneErr);
+
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+impl serde::Serialize for TaggedKind<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.priv_serialize(serializer, true)
+    }
+}
+
+impl OneErr {
+    fn priv_serialize<S>(
+        &self,
+        serializer: S,
+        tagged: bool,
+    ) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::SerializeMap;
 
         let (kind, os, inner) = self.priv_as_parts();
-        let error: &str = if let Some(os) = os {
-            if let ErrNo::Other = os {
-                if let Some(f) = &*inner.0 {
-                    if let Some(e_str) = f.get(ERROR) {
-                        if let Some(e_str) = e_str.as_str() {
-                            e_str
-                        } else {
-                            <&'static str>::from(os)
-                        }
-                    } else {
-                        <&'static str>::from(os)
-                    }
-                } else {
-                    <&'static str>::from(os)
-                }
-            } else {
-                <&'static str>::from(os)
-            }
+        let error: &str = if let Some(e_str) = inner.get_field::<&str>(ERROR) {
+            // custom str kinds, which may or may not have an io kind
+            e_str
+        } else if let Some(os) = os {
+            <&'static str>::from(os)
         } else {
-            if let std::io::ErrorKind::Other = kind {
-                if let Some(f) = &*inner.0 {
-                    if let Some(e_str) = f.get(ERROR) {
-                        if let Some(e_str) = e_str.as_str() {
-                            e_str
-                        } else {
-                            err_kind_to_str(kind)
-                        }
-                    } else {
-                        err_kind_to_str(kind)
-                    }
-                } else {
-                    err_kind_to_str(kind)
-                }
-            } else {
-                err_kind_to_str(kind)
-            }
+            err_kind_to_str(kind)
         };
 
         let mut count = 1;
@@ -211,7 +228,10 @@ impl serde::Serialize for OneErr {
 
         let mut map = serializer.serialize_map(Some(count))?;
 
-        map.serialize_entry(ERROR, &error)?;
+        match kind_to_tag(error) {
+            Some(tag) if tagged => map.serialize_entry(ERROR, &tag)?,
+            _ => map.serialize_entry(ERROR, &error)?,
+        }
 
         if let Some(f) = &*inner.0 {
             for (n, v) in f.iter() {
@@ -228,6 +248,33 @@ impl serde::Serialize for OneErr {
 
 impl<'de> serde::Deserialize<'de> for OneErr {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::priv_deserialize(deserializer, false)
+    }
+}
+
+/// Accepts well-known kinds as integer tags as well as strings,
+/// for the compact binary encodings.
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+pub(crate) struct FromTaggedKind(pub(crate) OneErr);
+
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+impl<'de> serde::Deserialize<'de> for FromTaggedKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        OneErr::priv_deserialize(deserializer, true).map(Self)
+    }
+}
+
+impl OneErr {
+    fn priv_deserialize<'de, D>(
+        deserializer: D,
+        tagged: bool,
+    ) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
@@ -263,35 +310,15 @@ impl<'de> serde::Deserialize<'de> for OneErr {
             }
         }
         let mut top_map: TopMap = deserializer.deserialize_map(V)?;
-        if let Some(error) = top_map.remove(ERROR) {
-            if let Some(error) = error.as_str() {
-                let (kind, os) = parse_err_str(error);
-                if let Some(os) = os {
-                    if let std::io::ErrorKind::Other = kind {
-                        top_map
-                            .insert(OS.into(), (i32::from(&os) as i64).into());
-                    }
-                } else {
-                    if let std::io::ErrorKind::Other = kind {
-                        if error != "Other" && error != "EOTHER" {
-                            top_map.insert(ERROR.into(), error.into());
-                        }
-                    }
-                }
-                if top_map.is_empty() {
-                    Ok(Self(std::io::Error::new(kind, OneErrInner::new())))
-                } else {
-                    Ok(Self(std::io::Error::new(
-                        kind,
-                        OneErrInner(Box::new(Some(top_map))),
-                    )))
-                }
-            } else {
-                Err(serde::de::Error::custom("required 'error' field is a str"))
+        if tagged {
+            if let Some(Value::U64(tag)) = top_map.get(ERROR) {
+                let kind = tag_to_kind(*tag).ok_or_else(|| {
+                    serde::de::Error::custom("unknown kind tag")
+                })?;
+                top_map.insert(ERROR.into(), kind.into());
             }
-        } else {
-            Err(serde::de::Error::custom("required 'error' field"))
         }
+        Self::priv_from_top_map(top_map).map_err(serde::de::Error::custom)
     }
 }
 
@@ -300,13 +327,14 @@ impl std::str::FromStr for OneErr {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         serde_json::from_str(s).map_err(|e| {
-            OneErr::with_message(crate::io_error::INVALID_DATA_STR, &e)
+            crate::parse_error::json_error(e, std::io::ErrorKind::InvalidData)
         })
     }
 }
 
 impl OneErr {
     /// Create a new OneErr error instance.
+    #[track_caller]
     pub fn new<K>(kind: K) -> Self
     where
         K: std::fmt::Display,
@@ -318,23 +346,24 @@ impl OneErr {
                 let mut inner = OneErrInner::new();
                 inner.set_field(ERROR.into(), kind_str);
 
-                Self(std::io::Error::new(kind, inner))
+                Self(std::io::Error::new(kind, inner)).priv_with_location()
             } else {
-                os.into()
+                Self::from(os)
             }
         } else {
-            if let std::io::ErrorKind::Other = kind {
+            if kind_str != err_kind_to_str(kind) {
                 let mut inner = OneErrInner::new();
                 inner.set_field(ERROR.into(), kind_str);
 
-                Self(std::io::Error::new(kind, inner))
+                Self(std::io::Error::new(kind, inner)).priv_with_location()
             } else {
-                kind.into()
+                Self::from(kind)
             }
         }
     }
 
     /// Create a new OneErr error instance with a message.
+    #[track_caller]
     pub fn with_message<K, M>(kind: K, message: M) -> Self
     where
         K: std::fmt::Display,
@@ -387,7 +416,7 @@ impl OneErr {
 
     /// Set an additional data field on this OneErr.
     /// Will panic on reserved names: "error", "os", "source",
-    /// "backtrace", "message".
+    /// "backtrace", "message", "location".
     pub fn set_field<K, T>(&mut self, name: &K, t: T) -> &mut Self
     where
         K: ?Sized + std::fmt::Display,
@@ -395,7 +424,7 @@ impl OneErr {
     {
         let name = name.to_string().into_boxed_str();
         match &*name {
-            ERROR | OS | SOURCE | BACKTRACE | MESSAGE => {
+            ERROR | OS | SOURCE | BACKTRACE | MESSAGE | LOCATION => {
                 panic!("field name '{}' is reserved", name)
             }
             _ => (),
@@ -405,6 +434,38 @@ impl OneErr {
         self
     }
 
+    /// Get the `file:line:column` source location this instance was
+    /// created at, if location capture was enabled at the time,
+    /// see [OneErr::set_capture_location].
+    pub fn location(&self) -> Option<&str> {
+        self.get_field(LOCATION)
+    }
+
+    /// Enable or disable, process wide, recording the source location
+    /// errors are created at. Disabled by default.
+    /// Applies to `OneErr::new`, `OneErr::with_message`, and the
+    /// `From` conversions into OneErr, including those invoked by `?`.
+    ///
+    /// ```rust
+    /// use one_err::*;
+    ///
+    /// OneErr::set_capture_location(true);
+    ///
+    /// let line = line!() + 1;
+    /// let err = OneErr::new("MyKind");
+    /// let expect = format!("{}:{}:", file!(), line);
+    /// assert!(err.location().unwrap().starts_with(&expect));
+    ///
+    /// let dec: OneErr = err.to_string().parse().unwrap();
+    /// assert_eq!(err.location(), dec.location());
+    ///
+    /// OneErr::set_capture_location(false);
+    /// assert_eq!(None, OneErr::new("MyKind").location());
+    /// ```
+    pub fn set_capture_location(enabled: bool) {
+        CAPTURE_LOCATION.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
     /// Get the message associated with this instance, or empty string.
     pub fn get_message(&self) -> Option<&str> {
         self.get_field(MESSAGE)
@@ -425,6 +486,54 @@ impl OneErr {
 // -- private -- //
 
 impl OneErr {
+    #[track_caller]
+    pub(crate) fn priv_with_location(mut self) -> Self {
+        let capture =
+            CAPTURE_LOCATION.load(std::sync::atomic::Ordering::Relaxed);
+        if capture && self.location().is_none() {
+            let loc = std::panic::Location::caller();
+            self.priv_as_inner_mut().set_field(
+                LOCATION.into(),
+                format!("{}:{}:{}", loc.file(), loc.line(), loc.column()),
+            );
+        }
+        self
+    }
+
+    /// Build an instance from a decoded field map,
+    /// which must hold the 'error' field as a kind str.
+    pub(crate) fn priv_from_top_map(
+        mut top_map: TopMap,
+    ) -> Result<Self, &'static str> {
+        if let Some(error) = top_map.remove(ERROR) {
+            if let Some(error) = error.as_str() {
+                let (kind, os) = parse_err_str(error);
+                if let Some(os) = os {
+                    if keep_errno(kind, os) {
+                        top_map
+                            .insert(OS.into(), (i32::from(&os) as i64).into());
+                    }
+                } else {
+                    if error != err_kind_to_str(kind) && error != "EOTHER" {
+                        top_map.insert(ERROR.into(), error.into());
+                    }
+                }
+                if top_map.is_empty() {
+                    Ok(Self(std::io::Error::new(kind, OneErrInner::new())))
+                } else {
+                    Ok(Self(std::io::Error::new(
+                        kind,
+                        OneErrInner(Box::new(Some(top_map))),
+                    )))
+                }
+            } else {
+                Err("required 'error' field is a str")
+            }
+        } else {
+            Err("required 'error' field")
+        }
+    }
+
     pub(crate) fn priv_as_inner(&self) -> &OneErrInner {
         // we can do all these unwraps because we control
         // our inner type to always be valid and of OneErrInner type.
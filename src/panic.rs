@@ -0,0 +1,152 @@
+//! Capture panics as OneErr instances.
+//!
+//! A captured panic becomes a [PANIC_STR] kind OneErr carrying the
+//! `panic_message`, the `location` the panic occurred at, and, when
+//! backtraces are enabled (e.g. `RUST_BACKTRACE=1`), the `backtrace`.
+//!
+//! The location and backtrace are recorded by a panic hook. [catch_unwind]
+//! installs it on first use, chaining to any previously installed hook,
+//! so the default panic output is unchanged.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::panic::*;
+//!
+//! let err = one_err::catch_unwind(|| {
+//!     panic!("oh no");
+//! })
+//! .unwrap_err();
+//!
+//! assert_eq!(PANIC_STR, err.str_kind());
+//! assert_eq!(Some("oh no"), err.get_field(PANIC_MESSAGE_FIELD));
+//! assert!(err.get_field::<_, &str>(LOCATION_FIELD).is_some());
+//! ```
+
+use crate::*;
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The const str 'Panic', `str_kind()` for captured panics.
+pub const PANIC_STR: &str = "Panic";
+
+/// Field holding the message the panic was raised with.
+pub const PANIC_MESSAGE_FIELD: &str = "panic_message";
+
+/// Field holding the `file:line:column` source location of the panic,
+/// also see [OneErr::location].
+pub const LOCATION_FIELD: &str = LOCATION;
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<OneErr>> = const { RefCell::new(None) };
+}
+
+static HOOK: std::sync::Once = std::sync::Once::new();
+
+/// Install a panic hook that converts each panic into a OneErr, passes it
+/// to `f`, then calls the previously installed hook.
+/// The recorded error is also what [catch_unwind] returns for that panic.
+///
+/// ```rust,no_run
+/// one_err::panic::install_panic_hook(|err| {
+///     // send it down the same channel as every other error
+///     eprintln!("{}", err);
+/// });
+/// ```
+pub fn install_panic_hook<F>(f: F)
+where
+    F: Fn(&OneErr) + Send + Sync + 'static,
+{
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let mut err = from_payload(info.payload());
+        if let Some(loc) = info.location() {
+            err.priv_as_inner_mut().set_field(
+                LOCATION.into(),
+                format!("{}:{}:{}", loc.file(), loc.line(), loc.column()),
+            );
+        }
+        let bt = std::backtrace::Backtrace::capture();
+        if let std::backtrace::BacktraceStatus::Captured = bt.status() {
+            err.priv_as_inner_mut()
+                .set_field(BACKTRACE.into(), bt.to_string());
+        }
+        f(&err);
+        LAST_PANIC.with(|l| *l.borrow_mut() = Some(err));
+        prev(info);
+    }));
+}
+
+/// Convert a panic payload, as returned from `std::panic::catch_unwind`,
+/// into a OneErr. This alone cannot know the location of the panic.
+pub fn from_payload(payload: &(dyn std::any::Any + Send)) -> OneErr {
+    let msg = if let Some(s) = payload.downcast_ref::<&'static str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "Box<dyn Any>"
+    };
+    let mut err = OneErr::new(PANIC_STR);
+    err.set_field(PANIC_MESSAGE_FIELD, msg);
+    err
+}
+
+fn ensure_hook() {
+    HOOK.call_once(|| install_panic_hook(|_| ()));
+}
+
+fn take_panic(payload: Box<dyn std::any::Any + Send>) -> OneErr {
+    LAST_PANIC
+        .with(|l| l.borrow_mut().take())
+        .unwrap_or_else(|| from_payload(&*payload))
+}
+
+/// Invoke `f`, capturing any panic as a [PANIC_STR] kind OneErr,
+/// see `std::panic::catch_unwind`.
+pub fn catch_unwind<F, R>(f: F) -> Result<R, OneErr>
+where
+    F: FnOnce() -> R + UnwindSafe,
+{
+    ensure_hook();
+    LAST_PANIC.with(|l| l.borrow_mut().take());
+    std::panic::catch_unwind(f).map_err(take_panic)
+}
+
+/// Poll `f`, capturing any panic as a [PANIC_STR] kind OneErr.
+/// The async equivalent of [catch_unwind].
+pub fn catch_unwind_async<F>(f: F) -> CatchUnwind<F>
+where
+    F: Future + UnwindSafe,
+{
+    ensure_hook();
+    CatchUnwind(Box::pin(f))
+}
+
+/// Future returned by [catch_unwind_async].
+pub struct CatchUnwind<F: Future>(Pin<Box<F>>);
+
+impl<F: Future> std::fmt::Debug for CatchUnwind<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CatchUnwind")
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, OneErr>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        let fut = self.0.as_mut();
+        LAST_PANIC.with(|l| l.borrow_mut().take());
+        match std::panic::catch_unwind(AssertUnwindSafe(|| fut.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(r)) => Poll::Ready(Ok(r)),
+            Err(payload) => Poll::Ready(Err(take_panic(payload))),
+        }
+    }
+}
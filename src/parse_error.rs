@@ -0,0 +1,248 @@
+//! Conversions from serde format parse errors into OneErr.
+//!
+//! Parse errors become `InvalidData` (or `UnexpectedEof` for truncated
+//! input) OneErr instances that keep the parser's position information
+//! as fields, so tooling can point at the offending input.
+//!
+//! The `toml`, `serde_yaml`, `rmp-serde`, `ciborium` and `prost`
+//! conversions are available behind the features of the same names.
+//! toml errors only carry a byte offset, use `toml_error` to also resolve
+//! it into a line and column against the parsed source.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::parse_error::*;
+//!
+//! let err: OneErr = serde_json::from_str::<i32>("\n  [").unwrap_err().into();
+//!
+//! assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+//! assert_eq!(Some(JSON), err.get_field(FORMAT_FIELD));
+//! assert_eq!(Some(DATA), err.get_field(CATEGORY_FIELD));
+//! assert_eq!(Some(2), err.get_field::<_, u64>(LINE_FIELD));
+//! assert_eq!(Some(2), err.get_field::<_, u64>(COLUMN_FIELD));
+//! ```
+
+use crate::*;
+
+/// Field naming the format that failed to parse.
+pub const FORMAT_FIELD: &str = "format";
+
+/// Field holding the category of a parse error, if known.
+pub const CATEGORY_FIELD: &str = "category";
+
+/// Field holding the 1-based line of a parse error, if known.
+pub const LINE_FIELD: &str = "line";
+
+/// Field holding the 1-based column of a parse error, if known.
+pub const COLUMN_FIELD: &str = "column";
+
+/// Field holding the byte offset of a parse error, if known.
+pub const OFFSET_FIELD: &str = "offset";
+
+/// `format` value for serde_json errors.
+pub const JSON: &str = "json";
+
+/// `format` value for toml errors.
+pub const TOML: &str = "toml";
+
+/// `format` value for serde_yaml errors.
+pub const YAML: &str = "yaml";
+
+/// `format` value for rmp-serde (MessagePack) errors.
+pub const MSGPACK: &str = "msgpack";
+
+/// `format` value for ciborium (CBOR) errors.
+pub const CBOR: &str = "cbor";
+
+/// `format` value for prost (Protocol Buffers) errors.
+pub const PROTOBUF: &str = "protobuf";
+
+/// `format` value for logfmt errors.
+pub const LOGFMT: &str = "logfmt";
+
+/// `format` value for compact binary encoding errors.
+pub const COMPACT: &str = "compact";
+
+/// `category` value for errors reading the underlying io stream.
+pub const IO: &str = "io";
+
+/// `category` value for syntactically invalid input.
+pub const SYNTAX: &str = "syntax";
+
+/// `category` value for input that does not match the expected data.
+pub const DATA: &str = "data";
+
+/// `category` value for input that ended prematurely.
+pub const EOF: &str = "eof";
+
+impl From<serde_json::Error> for OneErr {
+    #[track_caller]
+    fn from(e: serde_json::Error) -> Self {
+        json_error(e, std::io::ErrorKind::UnexpectedEof)
+    }
+}
+
+/// Convert a serde_json error, giving truncated input `eof_kind`.
+/// `FromStr` keeps reporting all parse failures as `InvalidData`.
+#[track_caller]
+pub(crate) fn json_error(
+    e: serde_json::Error,
+    eof_kind: std::io::ErrorKind,
+) -> OneErr {
+    use serde_json::error::Category;
+
+    let line = e.line();
+    let column = e.column();
+    let (category, kind) = match e.classify() {
+        Category::Io => (IO, None),
+        Category::Syntax => (SYNTAX, Some(std::io::ErrorKind::InvalidData)),
+        Category::Data => (DATA, Some(std::io::ErrorKind::InvalidData)),
+        Category::Eof => (EOF, Some(eof_kind)),
+    };
+
+    let mut err = match kind {
+        Some(kind) => OneErr::with_message(err_kind_to_str(kind), &e),
+        None => OneErr::from(std::io::Error::from(e)),
+    };
+    err.set_field(FORMAT_FIELD, JSON)
+        .set_field(CATEGORY_FIELD, category);
+    if line > 0 {
+        err.set_field(LINE_FIELD, line as u64)
+            .set_field(COLUMN_FIELD, column as u64);
+    }
+    err
+}
+
+/// Convert a toml error into a OneErr, resolving the error's byte offset
+/// into `line` and `column` fields against the source that was parsed.
+/// The plain `From` conversion only knows the byte offset.
+#[cfg(feature = "toml")]
+#[track_caller]
+pub fn toml_error(e: toml::de::Error, source: &str) -> OneErr {
+    let offset = e.span().map(|s| s.start);
+    let mut err = OneErr::from(e);
+    if let Some(offset) = offset {
+        let (line, column) = line_column(source, offset);
+        err.set_field(LINE_FIELD, line)
+            .set_field(COLUMN_FIELD, column);
+    }
+    err
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::de::Error> for OneErr {
+    #[track_caller]
+    fn from(e: toml::de::Error) -> Self {
+        let mut err = OneErr::with_message(
+            crate::io_error::INVALID_DATA_STR,
+            e.message(),
+        );
+        err.set_field(FORMAT_FIELD, TOML);
+        if let Some(span) = e.span() {
+            err.set_field(OFFSET_FIELD, span.start as u64);
+        }
+        err
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+impl From<serde_yaml::Error> for OneErr {
+    #[track_caller]
+    fn from(e: serde_yaml::Error) -> Self {
+        let mut err =
+            OneErr::with_message(crate::io_error::INVALID_DATA_STR, &e);
+        err.set_field(FORMAT_FIELD, YAML);
+        if let Some(loc) = e.location() {
+            err.set_field(LINE_FIELD, loc.line() as u64)
+                .set_field(COLUMN_FIELD, loc.column() as u64)
+                .set_field(OFFSET_FIELD, loc.index() as u64);
+        }
+        err
+    }
+}
+
+#[cfg(feature = "rmp-serde")]
+impl From<rmp_serde::decode::Error> for OneErr {
+    #[track_caller]
+    fn from(e: rmp_serde::decode::Error) -> Self {
+        use rmp_serde::decode::Error::*;
+        let (category, kind) = match &e {
+            InvalidMarkerRead(io) | InvalidDataRead(io)
+                if io.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                (EOF, std::io::ErrorKind::UnexpectedEof)
+            }
+            InvalidMarkerRead(_) | InvalidDataRead(_) => {
+                (IO, std::io::ErrorKind::InvalidData)
+            }
+            Syntax(_) => (SYNTAX, std::io::ErrorKind::InvalidData),
+            _ => (DATA, std::io::ErrorKind::InvalidData),
+        };
+        let mut err = OneErr::with_message(err_kind_to_str(kind), &e);
+        err.set_field(FORMAT_FIELD, MSGPACK)
+            .set_field(CATEGORY_FIELD, category);
+        err
+    }
+}
+
+#[cfg(feature = "ciborium")]
+impl From<ciborium::de::Error<std::io::Error>> for OneErr {
+    #[track_caller]
+    fn from(e: ciborium::de::Error<std::io::Error>) -> Self {
+        use ciborium::de::Error::*;
+        let (category, kind, offset) = match &e {
+            Io(io) if io.kind() == std::io::ErrorKind::UnexpectedEof => {
+                (EOF, std::io::ErrorKind::UnexpectedEof, None)
+            }
+            Io(_) => (IO, std::io::ErrorKind::InvalidData, None),
+            Syntax(offset) => {
+                (SYNTAX, std::io::ErrorKind::InvalidData, Some(*offset))
+            }
+            Semantic(offset, _) => {
+                (DATA, std::io::ErrorKind::InvalidData, *offset)
+            }
+            RecursionLimitExceeded => {
+                (DATA, std::io::ErrorKind::InvalidData, None)
+            }
+        };
+        let message = match &e {
+            Io(io) => io.to_string(),
+            Syntax(_) => "invalid CBOR syntax".to_string(),
+            Semantic(_, msg) => msg.clone(),
+            RecursionLimitExceeded => "recursion limit exceeded".to_string(),
+        };
+        let mut err = OneErr::with_message(err_kind_to_str(kind), message);
+        err.set_field(FORMAT_FIELD, CBOR)
+            .set_field(CATEGORY_FIELD, category);
+        if let Some(offset) = offset {
+            err.set_field(OFFSET_FIELD, offset as u64);
+        }
+        err
+    }
+}
+
+/// 1-based line and column (in chars) of a byte offset into source.
+#[cfg(feature = "toml")]
+fn line_column(source: &str, offset: usize) -> (u64, u64) {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line as u64, column as u64)
+}
+
+#[cfg(feature = "prost")]
+impl From<prost::DecodeError> for OneErr {
+    #[track_caller]
+    fn from(e: prost::DecodeError) -> Self {
+        let mut err =
+            OneErr::with_message(crate::io_error::INVALID_DATA_STR, &e);
+        err.set_field(FORMAT_FIELD, PROTOBUF)
+            .set_field(CATEGORY_FIELD, DATA);
+        err
+    }
+}
@@ -0,0 +1,158 @@
+use crate::*;
+
+/// An operating system errno numbering scheme.
+///
+/// ErrNo's own numeric values follow the libc of the current target.
+/// Use [ErrNo::from_platform_code] / [ErrNo::to_platform_code] to
+/// translate raw errno numbers produced on, or destined for, some
+/// other platform.
+///
+/// ```rust
+/// use one_err::*;
+///
+/// // EAGAIN on macos
+/// assert_eq!(
+///     ErrNo::WouldBlock,
+///     ErrNo::from_platform_code(Platform::Darwin, 35),
+/// );
+/// assert_eq!(Some(11), ErrNo::WouldBlock.to_platform_code(Platform::Linux));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Platform {
+    /// Linux on the generic architectures (x86, arm, riscv, ...).
+    Linux,
+
+    /// macOS / iOS.
+    Darwin,
+
+    /// FreeBSD.
+    FreeBsd,
+
+    /// OpenBSD.
+    OpenBsd,
+
+    /// The errno values of the Windows C runtime (not Win32 error codes).
+    WindowsCrt,
+}
+
+// marks a code not defined on a platform
+const __: i32 = 0;
+
+// per-platform codes, columns are indexed by `Platform as usize`
+#[rustfmt::skip]
+const PLATFORM_TABLE: &[(ErrNo, [i32; 5])] = &[
+    (ErrNo::Perm, [1, 1, 1, 1, 1]),
+    (ErrNo::NoEnt, [2, 2, 2, 2, 2]),
+    (ErrNo::Srch, [3, 3, 3, 3, 3]),
+    (ErrNo::Intr, [4, 4, 4, 4, 4]),
+    (ErrNo::Io, [5, 5, 5, 5, 5]),
+    (ErrNo::NxIo, [6, 6, 6, 6, 6]),
+    (ErrNo::TooBig, [7, 7, 7, 7, 7]),
+    (ErrNo::NoExec, [8, 8, 8, 8, 8]),
+    (ErrNo::BadF, [9, 9, 9, 9, 9]),
+    (ErrNo::Child, [10, 10, 10, 10, 10]),
+    (ErrNo::NoMem, [12, 12, 12, 12, 12]),
+    (ErrNo::Fault, [14, 14, 14, 14, 14]),
+    (ErrNo::Busy, [16, 16, 16, 16, 16]),
+    (ErrNo::Exist, [17, 17, 17, 17, 17]),
+    (ErrNo::XDev, [18, 18, 18, 18, 18]),
+    (ErrNo::NoDev, [19, 19, 19, 19, 19]),
+    (ErrNo::NotDir, [20, 20, 20, 20, 20]),
+    (ErrNo::IsDir, [21, 21, 21, 21, 21]),
+    (ErrNo::Inval, [22, 22, 22, 22, 22]),
+    (ErrNo::NFile, [23, 23, 23, 23, 23]),
+    (ErrNo::MFile, [24, 24, 24, 24, 24]),
+    (ErrNo::NotTy, [25, 25, 25, 25, 25]),
+    (ErrNo::TxtBsy, [26, 26, 26, 26, 139]),
+    (ErrNo::FBig, [27, 27, 27, 27, 27]),
+    (ErrNo::NoSpc, [28, 28, 28, 28, 28]),
+    (ErrNo::SPipe, [29, 29, 29, 29, 29]),
+    (ErrNo::ROFS, [30, 30, 30, 30, 30]),
+    (ErrNo::MLink, [31, 31, 31, 31, 31]),
+    (ErrNo::Pipe, [32, 32, 32, 32, 32]),
+    (ErrNo::Dom, [33, 33, 33, 33, 33]),
+    (ErrNo::Range, [34, 34, 34, 34, 34]),
+    (ErrNo::DeadLk, [35, 11, 11, 11, 36]),
+    (ErrNo::NameTooLong, [36, 63, 63, 63, 38]),
+    (ErrNo::NoLck, [37, 77, 77, 77, 39]),
+    (ErrNo::NoSys, [38, 78, 78, 78, 40]),
+    (ErrNo::NotEmpty, [39, 66, 66, 66, 41]),
+    (ErrNo::Loop, [40, 62, 62, 62, 114]),
+    (ErrNo::WouldBlock, [11, 35, 35, 35, 11]),
+    (ErrNo::NoMsg, [42, 91, 83, 90, 122]),
+    (ErrNo::IdRm, [43, 90, 82, 89, 111]),
+    (ErrNo::NoStr, [60, 99, __, __, 125]),
+    (ErrNo::NoData, [61, 96, __, __, 120]),
+    (ErrNo::Time, [62, 101, __, __, 137]),
+    (ErrNo::NoSR, [63, 98, __, __, 124]),
+    (ErrNo::NoLink, [67, 97, 91, __, 121]),
+    (ErrNo::Proto, [71, 100, 92, 95, 134]),
+    (ErrNo::BadMsg, [74, 94, 89, 92, 104]),
+    (ErrNo::Overflow, [75, 84, 84, 87, 132]),
+    (ErrNo::IlSeq, [84, 92, 86, 84, 42]),
+    (ErrNo::NotSock, [88, 38, 38, 38, 128]),
+    (ErrNo::DestAddrReq, [89, 39, 39, 39, 109]),
+    (ErrNo::MsgSize, [90, 40, 40, 40, 115]),
+    (ErrNo::ProtoType, [91, 41, 41, 41, 136]),
+    (ErrNo::NoProtoOpt, [92, 42, 42, 42, 123]),
+    (ErrNo::ProtoNoSupport, [93, 43, 43, 43, 135]),
+    (ErrNo::OpNotSupp, [95, 102, 45, 45, 130]),
+    (ErrNo::AFNoSupport, [97, 47, 47, 47, 102]),
+    (ErrNo::AddrInUse, [98, 48, 48, 48, 100]),
+    (ErrNo::AddrNotAvail, [99, 49, 49, 49, 101]),
+    (ErrNo::NetDown, [100, 50, 50, 50, 116]),
+    (ErrNo::NetUnreach, [101, 51, 51, 51, 118]),
+    (ErrNo::NetReset, [102, 52, 52, 52, 117]),
+    (ErrNo::ConnAborted, [103, 53, 53, 53, 106]),
+    (ErrNo::ConnReset, [104, 54, 54, 54, 108]),
+    (ErrNo::NoBufS, [105, 55, 55, 55, 119]),
+    (ErrNo::IsConn, [106, 56, 56, 56, 113]),
+    (ErrNo::NotConn, [107, 57, 57, 57, 126]),
+    (ErrNo::TimedOut, [110, 60, 60, 60, 138]),
+    (ErrNo::ConnRefused, [111, 61, 61, 61, 107]),
+    (ErrNo::HostUnreach, [113, 65, 65, 65, 110]),
+    (ErrNo::Already, [114, 37, 37, 37, 103]),
+    (ErrNo::InProgress, [115, 36, 36, 36, 112]),
+    (ErrNo::Stale, [116, 70, 70, 70, __]),
+    (ErrNo::DQuot, [122, 69, 69, 69, __]),
+];
+
+// additional codes that decode to an ErrNo, but are never encoded
+#[rustfmt::skip]
+const PLATFORM_ALIASES: &[(ErrNo, [i32; 5])] = &[
+    // EACCES
+    (ErrNo::Perm, [13, 13, 13, 13, 13]),
+    // EWOULDBLOCK
+    (ErrNo::WouldBlock, [__, __, __, __, 140]),
+    // ENOTSUP
+    (ErrNo::OpNotSupp, [__, 45, __, 91, 129]),
+];
+
+impl ErrNo {
+    /// Translate a raw errno number from the given platform.
+    /// Unknown codes become `ErrNo::Other`.
+    pub fn from_platform_code(platform: Platform, code: i32) -> Self {
+        if code == __ {
+            return Self::Other;
+        }
+        let col = platform as usize;
+        PLATFORM_TABLE
+            .iter()
+            .chain(PLATFORM_ALIASES.iter())
+            .find(|(_, codes)| codes[col] == code)
+            .map(|(errno, _)| *errno)
+            .unwrap_or(Self::Other)
+    }
+
+    /// The raw errno number of this ErrNo on the given platform,
+    /// if that platform defines it.
+    pub fn to_platform_code(&self, platform: Platform) -> Option<i32> {
+        let col = platform as usize;
+        PLATFORM_TABLE
+            .iter()
+            .find(|(errno, _)| errno == self)
+            .map(|(_, codes)| codes[col])
+            .filter(|code| *code != __)
+    }
+}
@@ -0,0 +1,139 @@
+//! Helpers for running `std::process::Command` with OneErr errors.
+//!
+//! Spawn failures keep the errno of the failure. Processes that exit
+//! unsuccessfully become a [PROCESS_FAILED_STR] kind OneErr. Both carry
+//! `program` and `args` fields, and failed processes additionally
+//! carry `exit_code` or `signal`, plus a bounded `stderr_tail` when
+//! stderr was captured.
+//!
+//! ```rust
+//! # #[cfg(unix)]
+//! # {
+//! use one_err::*;
+//! use one_err::process::*;
+//!
+//! let err = output(
+//!     std::process::Command::new("sh").args(["-c", "echo oops >&2; exit 3"]),
+//! )
+//! .unwrap_err();
+//!
+//! assert_eq!(PROCESS_FAILED_STR, err.str_kind());
+//! assert_eq!(Some("sh"), err.get_field(PROGRAM_FIELD));
+//! assert_eq!(Some(3), err.get_field::<_, i64>(EXIT_CODE_FIELD));
+//! assert_eq!(Some("oops\n"), err.get_field(STDERR_TAIL_FIELD));
+//! # }
+//! ```
+
+use crate::*;
+use std::process::{Command, ExitStatus, Output};
+
+/// The const str 'ProcessFailed', `str_kind()` for processes that
+/// did not exit successfully.
+pub const PROCESS_FAILED_STR: &str = "ProcessFailed";
+
+/// Field holding the program that was run.
+pub const PROGRAM_FIELD: &str = "program";
+
+/// Field holding the arguments of the program, as a json array string.
+pub const ARGS_FIELD: &str = "args";
+
+/// Field holding the exit code of a failed process, if any.
+pub const EXIT_CODE_FIELD: &str = "exit_code";
+
+/// Field holding the signal that terminated a failed process, if any.
+pub const SIGNAL_FIELD: &str = "signal";
+
+/// Field holding the end of the captured stderr of a failed process.
+pub const STDERR_TAIL_FIELD: &str = "stderr_tail";
+
+/// Maximum byte length of the `stderr_tail` field.
+pub const STDERR_TAIL_MAX: usize = 4096;
+
+/// Run the command to completion capturing its output,
+/// see `std::process::Command::output`.
+#[track_caller]
+pub fn output(cmd: &mut Command) -> Result<Output, OneErr> {
+    let out = match cmd.output() {
+        Ok(out) => out,
+        Err(e) => return Err(spawn_err(cmd, e)),
+    };
+    check_output(cmd, out)
+}
+
+/// Run the command to completion, see `std::process::Command::status`.
+#[track_caller]
+pub fn status(cmd: &mut Command) -> Result<ExitStatus, OneErr> {
+    let status = match cmd.status() {
+        Ok(status) => status,
+        Err(e) => return Err(spawn_err(cmd, e)),
+    };
+    check_status(cmd, status)
+}
+
+/// Check the output of a command that has already been run.
+#[track_caller]
+pub fn check_output(cmd: &Command, output: Output) -> Result<Output, OneErr> {
+    if output.status.success() {
+        return Ok(output);
+    }
+    let mut err = exit_err(cmd, output.status);
+    if !output.stderr.is_empty() {
+        err.set_field(STDERR_TAIL_FIELD, stderr_tail(&output.stderr));
+    }
+    Err(err)
+}
+
+/// Check the exit status of a command that has already been run.
+#[track_caller]
+pub fn check_status(
+    cmd: &Command,
+    status: ExitStatus,
+) -> Result<ExitStatus, OneErr> {
+    if status.success() {
+        return Ok(status);
+    }
+    Err(exit_err(cmd, status))
+}
+
+fn set_cmd_fields(err: &mut OneErr, cmd: &Command) {
+    let args: Vec<String> = cmd
+        .get_args()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    err.set_field(PROGRAM_FIELD, cmd.get_program().to_string_lossy().as_ref())
+        .set_field(
+            ARGS_FIELD,
+            serde_json::to_string(&args).unwrap_or_default(),
+        );
+}
+
+#[track_caller]
+fn spawn_err(cmd: &Command, e: std::io::Error) -> OneErr {
+    let mut err = OneErr::from(e);
+    set_cmd_fields(&mut err, cmd);
+    err
+}
+
+#[track_caller]
+fn exit_err(cmd: &Command, status: ExitStatus) -> OneErr {
+    let mut err = OneErr::with_message(PROCESS_FAILED_STR, status);
+    set_cmd_fields(&mut err, cmd);
+    if let Some(code) = status.code() {
+        err.set_field(EXIT_CODE_FIELD, code as i64);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            err.set_field(SIGNAL_FIELD, signal as i64);
+        }
+    }
+    err
+}
+
+fn stderr_tail(stderr: &[u8]) -> String {
+    let tail = &stderr[stderr.len().saturating_sub(STDERR_TAIL_MAX)..];
+    let tail = String::from_utf8_lossy(tail);
+    // a cut multi-byte char shows up as a leading replacement char
+    tail.trim_start_matches('\u{fffd}').to_string()
+}
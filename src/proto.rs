@@ -0,0 +1,225 @@
+//! Protocol Buffers messages for OneErr, behind the `prost` feature.
+//!
+//! The schema is `proto/one_err.proto` in this crate's repository,
+//! use it to generate matching types in other languages. The types here
+//! are what prost-build generates for that schema.
+//!
+//! The `kind` field is authoritative, the `io_kind` and `errno` fields
+//! are informational for consumers that cannot parse kinds themselves.
+//! A nested `source` is kept, json encoded, in the reserved "source" field,
+//! so it survives decoding and re-encoding.
+//!
+//! Decoding is fallible: `fields` may not use the names reserved for the
+//! other message fields ("error", "os", "message", "source"), and
+//! "location" and "backtrace" must be strings.
+//!
+//! ```rust
+//! use one_err::*;
+//!
+//! let mut err = OneErr::with_message("NotFound", "no such thing");
+//! err.set_field("path", "/tmp");
+//!
+//! let msg = proto::OneErr::from(&err);
+//! assert_eq!("NotFound", msg.kind);
+//! assert_eq!("ENOENT", msg.errno);
+//!
+//! let dec = OneErr::from_protobuf(&err.to_protobuf()).unwrap();
+//! assert_eq!(err, dec);
+//! ```
+
+use crate::*;
+use prost::Message;
+use std::convert::TryFrom;
+
+/// A serialized OneErr.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OneErr {
+    /// The `str_kind()`, e.g. "NotFound", "ENOENT", or a custom kind.
+    #[prost(string, tag = "1")]
+    pub kind: String,
+
+    /// The std::io::ErrorKind name, e.g. "NotFound", or "Other".
+    #[prost(string, tag = "2")]
+    pub io_kind: String,
+
+    /// The ErrNo name, e.g. "ENOENT", or "EOTHER".
+    #[prost(string, tag = "3")]
+    pub errno: String,
+
+    /// The human-readable message, if any.
+    #[prost(string, tag = "4")]
+    pub message: String,
+
+    /// Additional data fields.
+    #[prost(btree_map = "string, message", tag = "5")]
+    pub fields: std::collections::BTreeMap<String, FieldValue>,
+
+    /// The error that caused this one, if any.
+    #[prost(message, optional, boxed, tag = "6")]
+    pub source: Option<Box<OneErr>>,
+}
+
+/// A typed field value. An unset value is null.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FieldValue {
+    /// The value, if not null.
+    #[prost(oneof = "field_value::Value", tags = "1, 2, 3, 4, 5")]
+    pub value: Option<field_value::Value>,
+}
+
+/// Nested types of [FieldValue].
+pub mod field_value {
+    /// The value of a [FieldValue](super::FieldValue).
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Value {
+        /// Boolean Type
+        #[prost(bool, tag = "1")]
+        BoolValue(bool),
+
+        /// Signed Integer Type
+        #[prost(sint64, tag = "2")]
+        I64Value(i64),
+
+        /// Unsigned Integer Type
+        #[prost(uint64, tag = "3")]
+        U64Value(u64),
+
+        /// Floating Point Type
+        #[prost(double, tag = "4")]
+        F64Value(f64),
+
+        /// String Type
+        #[prost(string, tag = "5")]
+        StringValue(String),
+    }
+}
+
+impl From<&crate::Value> for FieldValue {
+    fn from(v: &crate::Value) -> Self {
+        use field_value::Value as V;
+        let value = match v {
+            crate::Value::Null => None,
+            crate::Value::Bool(b) => Some(V::BoolValue(*b)),
+            crate::Value::I64(i) => Some(V::I64Value(*i)),
+            crate::Value::U64(u) => Some(V::U64Value(*u)),
+            crate::Value::F64(f) => Some(V::F64Value(*f)),
+            crate::Value::String(s) => Some(V::StringValue(s.to_string())),
+        };
+        Self { value }
+    }
+}
+
+impl From<FieldValue> for crate::Value {
+    fn from(v: FieldValue) -> Self {
+        use field_value::Value as V;
+        match v.value {
+            None => crate::Value::Null,
+            Some(V::BoolValue(b)) => b.into(),
+            Some(V::I64Value(i)) => i.into(),
+            Some(V::U64Value(u)) => u.into(),
+            Some(V::F64Value(f)) => f.into(),
+            Some(V::StringValue(s)) => s.into(),
+        }
+    }
+}
+
+impl From<&crate::OneErr> for OneErr {
+    fn from(e: &crate::OneErr) -> Self {
+        let mut fields = std::collections::BTreeMap::new();
+        if let Some(f) = &*e.priv_as_inner().0 {
+            for (n, v) in f.iter() {
+                match &**n {
+                    ERROR | OS | MESSAGE | SOURCE => continue,
+                    _ => {
+                        fields.insert(n.to_string(), v.into());
+                    }
+                }
+            }
+        }
+        let source = e
+            .get_field::<_, &str>(SOURCE)
+            .and_then(|s| s.parse::<crate::OneErr>().ok())
+            .map(|s| Box::new(OneErr::from(&s)));
+        Self {
+            kind: e.str_kind().to_string(),
+            io_kind: err_kind_to_str(e.io_kind()).to_string(),
+            errno: <&'static str>::from(e.errno()).to_string(),
+            message: e.get_message().unwrap_or_default().to_string(),
+            fields,
+            source,
+        }
+    }
+}
+
+impl TryFrom<OneErr> for crate::OneErr {
+    type Error = crate::OneErr;
+
+    fn try_from(e: OneErr) -> Result<Self, Self::Error> {
+        // producers in other languages may only know the errno or io kind
+        let kind = if !e.kind.is_empty() {
+            e.kind
+        } else if !e.errno.is_empty() && e.errno != "EOTHER" {
+            e.errno
+        } else if !e.io_kind.is_empty() {
+            e.io_kind
+        } else {
+            crate::io_error::OTHER_STR.to_string()
+        };
+
+        let mut top_map = TopMap::new();
+        top_map.insert(ERROR.into(), kind.into());
+        if !e.message.is_empty() {
+            top_map.insert(MESSAGE.into(), e.message.into());
+        }
+        for (n, v) in e.fields {
+            let v = crate::Value::from(v);
+            match (n.as_str(), &v) {
+                (ERROR | OS | MESSAGE | SOURCE, _) => {
+                    return Err(invalid(format!(
+                        "field name '{}' is reserved",
+                        n
+                    )));
+                }
+                (LOCATION | BACKTRACE, crate::Value::String(_)) => (),
+                (LOCATION | BACKTRACE, _) => {
+                    return Err(invalid(format!(
+                        "field '{}' must be a string",
+                        n
+                    )));
+                }
+                _ => (),
+            }
+            top_map.insert(n.into_boxed_str(), v);
+        }
+        if let Some(source) = e.source {
+            let source = crate::OneErr::try_from(*source)?;
+            top_map.insert(SOURCE.into(), source.to_string().into());
+        }
+
+        crate::OneErr::priv_from_top_map(top_map).map_err(invalid)
+    }
+}
+
+fn invalid(message: impl std::fmt::Display) -> crate::OneErr {
+    let mut err =
+        crate::OneErr::with_message(crate::io_error::INVALID_DATA_STR, message);
+    err.set_field(
+        crate::parse_error::FORMAT_FIELD,
+        crate::parse_error::PROTOBUF,
+    )
+    .set_field(crate::parse_error::CATEGORY_FIELD, crate::parse_error::DATA);
+    err
+}
+
+impl crate::OneErr {
+    /// Encode this instance as a Protocol Buffers [proto::OneErr] message.
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        OneErr::from(self).encode_to_vec()
+    }
+
+    /// Decode an instance from a Protocol Buffers [proto::OneErr] message.
+    pub fn from_protobuf(data: &[u8]) -> Result<Self, crate::OneErr> {
+        let msg = OneErr::decode(data).map_err(crate::OneErr::from)?;
+        crate::OneErr::try_from(msg)
+    }
+}
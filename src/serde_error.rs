@@ -0,0 +1,203 @@
+//! `serde::ser::Error` and `serde::de::Error` support for OneErr.
+//!
+//! OneErr can be used directly as the error type of a custom
+//! serde `Serializer` or `Deserializer`. All such errors are of kind
+//! `InvalidData`, carry a `message` equivalent to the serde default
+//! message, and a `serde_error` field naming the serde error constructor.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::serde_error::*;
+//! use serde::de::{Deserialize, IntoDeserializer};
+//!
+//! let d: serde::de::value::StrDeserializer<OneErr> =
+//!     "yes".into_deserializer();
+//! let err = bool::deserialize(d).unwrap_err();
+//!
+//! assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+//! assert_eq!(Some(INVALID_TYPE), err.get_field(SERDE_ERROR_FIELD));
+//! assert_eq!(Some("string \"yes\""), err.get_field(UNEXPECTED_FIELD));
+//! assert_eq!(Some("a boolean"), err.get_field(EXPECTED_FIELD));
+//! ```
+
+use crate::*;
+
+/// Field naming the serde error constructor that produced this error.
+pub const SERDE_ERROR_FIELD: &str = "serde_error";
+
+/// Field holding the description of an unexpected value.
+pub const UNEXPECTED_FIELD: &str = "unexpected";
+
+/// Field holding the description of what was expected.
+pub const EXPECTED_FIELD: &str = "expected";
+
+/// Field holding an invalid length.
+pub const LEN_FIELD: &str = "len";
+
+/// Field holding an unknown variant name.
+pub const VARIANT_FIELD: &str = "variant";
+
+/// Field holding a missing, duplicate, or unknown field name.
+pub const FIELD_FIELD: &str = "field";
+
+/// `serde_error` value for `custom` errors.
+pub const CUSTOM: &str = "custom";
+
+/// `serde_error` value for `invalid_type` errors.
+pub const INVALID_TYPE: &str = "invalid_type";
+
+/// `serde_error` value for `invalid_value` errors.
+pub const INVALID_VALUE: &str = "invalid_value";
+
+/// `serde_error` value for `invalid_length` errors.
+pub const INVALID_LENGTH: &str = "invalid_length";
+
+/// `serde_error` value for `unknown_variant` errors.
+pub const UNKNOWN_VARIANT: &str = "unknown_variant";
+
+/// `serde_error` value for `unknown_field` errors.
+pub const UNKNOWN_FIELD: &str = "unknown_field";
+
+/// `serde_error` value for `missing_field` errors.
+pub const MISSING_FIELD: &str = "missing_field";
+
+/// `serde_error` value for `duplicate_field` errors.
+pub const DUPLICATE_FIELD: &str = "duplicate_field";
+
+#[track_caller]
+fn serde_err<M>(serde_error: &'static str, message: M) -> OneErr
+where
+    M: std::fmt::Display,
+{
+    let mut err =
+        OneErr::with_message(crate::io_error::INVALID_DATA_STR, message);
+    err.set_field(SERDE_ERROR_FIELD, serde_error);
+    err
+}
+
+fn one_of(names: &'static [&'static str]) -> String {
+    match names.len() {
+        0 => "there are none".to_string(),
+        1 => format!("`{}`", names[0]),
+        2 => format!("`{}` or `{}`", names[0], names[1]),
+        _ => {
+            let mut out = "one of ".to_string();
+            for (i, n) in names.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push('`');
+                out.push_str(n);
+                out.push('`');
+            }
+            out
+        }
+    }
+}
+
+impl serde::ser::Error for OneErr {
+    #[track_caller]
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        serde_err(CUSTOM, msg)
+    }
+}
+
+impl serde::de::Error for OneErr {
+    #[track_caller]
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        serde_err(CUSTOM, msg)
+    }
+
+    #[track_caller]
+    fn invalid_type(
+        unexp: serde::de::Unexpected<'_>,
+        exp: &dyn serde::de::Expected,
+    ) -> Self {
+        let mut err = serde_err(
+            INVALID_TYPE,
+            format_args!("invalid type: {}, expected {}", unexp, exp),
+        );
+        err.set_field(UNEXPECTED_FIELD, unexp.to_string())
+            .set_field(EXPECTED_FIELD, exp.to_string());
+        err
+    }
+
+    #[track_caller]
+    fn invalid_value(
+        unexp: serde::de::Unexpected<'_>,
+        exp: &dyn serde::de::Expected,
+    ) -> Self {
+        let mut err = serde_err(
+            INVALID_VALUE,
+            format_args!("invalid value: {}, expected {}", unexp, exp),
+        );
+        err.set_field(UNEXPECTED_FIELD, unexp.to_string())
+            .set_field(EXPECTED_FIELD, exp.to_string());
+        err
+    }
+
+    #[track_caller]
+    fn invalid_length(len: usize, exp: &dyn serde::de::Expected) -> Self {
+        let mut err = serde_err(
+            INVALID_LENGTH,
+            format_args!("invalid length {}, expected {}", len, exp),
+        );
+        err.set_field(LEN_FIELD, len as u64)
+            .set_field(EXPECTED_FIELD, exp.to_string());
+        err
+    }
+
+    #[track_caller]
+    fn unknown_variant(
+        variant: &str,
+        expected: &'static [&'static str],
+    ) -> Self {
+        let expected = one_of(expected);
+        let mut err = serde_err(
+            UNKNOWN_VARIANT,
+            format_args!(
+                "unknown variant `{}`, expected {}",
+                variant, expected
+            ),
+        );
+        err.set_field(VARIANT_FIELD, variant)
+            .set_field(EXPECTED_FIELD, expected);
+        err
+    }
+
+    #[track_caller]
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        let expected = one_of(expected);
+        let mut err = serde_err(
+            UNKNOWN_FIELD,
+            format_args!("unknown field `{}`, expected {}", field, expected),
+        );
+        err.set_field(FIELD_FIELD, field)
+            .set_field(EXPECTED_FIELD, expected);
+        err
+    }
+
+    #[track_caller]
+    fn missing_field(field: &'static str) -> Self {
+        let mut err =
+            serde_err(MISSING_FIELD, format_args!("missing field `{}`", field));
+        err.set_field(FIELD_FIELD, field);
+        err
+    }
+
+    #[track_caller]
+    fn duplicate_field(field: &'static str) -> Self {
+        let mut err = serde_err(
+            DUPLICATE_FIELD,
+            format_args!("duplicate field `{}`", field),
+        );
+        err.set_field(FIELD_FIELD, field);
+        err
+    }
+}
@@ -0,0 +1,173 @@
+//! Conversions from common std error types into OneErr.
+//!
+//! Each conversion picks an appropriate `std::io::ErrorKind`, keeps the
+//! original error text as the message, and records any structured
+//! information as additional fields.
+//!
+//! ```rust
+//! use one_err::*;
+//! use one_err::std_error::*;
+//!
+//! let bad = vec![b'a', 0xff, b'b'];
+//! let err = OneErr::from(std::str::from_utf8(&bad).unwrap_err());
+//!
+//! assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+//! assert_eq!(Some(1), err.get_field::<_, u64>(VALID_UP_TO_FIELD));
+//! assert_eq!(Some(1), err.get_field::<_, u64>(ERROR_LEN_FIELD));
+//! ```
+
+use crate::*;
+
+/// The const str 'Poisoned', `str_kind()` for `PoisonError` conversions.
+pub const POISONED_STR: &str = "Poisoned";
+
+/// Field holding the `IntErrorKind` of a `ParseIntError`.
+pub const INT_ERROR_KIND_FIELD: &str = "int_error_kind";
+
+/// Field holding the `valid_up_to()` of a utf8 error.
+pub const VALID_UP_TO_FIELD: &str = "valid_up_to";
+
+/// Field holding the `error_len()` of a utf8 error, if any.
+pub const ERROR_LEN_FIELD: &str = "error_len";
+
+/// Field holding the `nul_position()` of a `NulError`.
+pub const NUL_POSITION_FIELD: &str = "nul_position";
+
+/// Field holding the `duration()` of a `SystemTimeError` in seconds.
+pub const DURATION_SECS_FIELD: &str = "duration_secs";
+
+#[track_caller]
+fn with_kind<M>(kind: std::io::ErrorKind, message: M) -> OneErr
+where
+    M: std::fmt::Display,
+{
+    OneErr::with_message(err_kind_to_str(kind), message)
+}
+
+impl From<std::num::ParseIntError> for OneErr {
+    #[track_caller]
+    fn from(e: std::num::ParseIntError) -> Self {
+        let mut err = with_kind(std::io::ErrorKind::InvalidData, &e);
+        err.set_field(INT_ERROR_KIND_FIELD, format!("{:?}", e.kind()));
+        err
+    }
+}
+
+impl From<std::num::ParseFloatError> for OneErr {
+    #[track_caller]
+    fn from(e: std::num::ParseFloatError) -> Self {
+        with_kind(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+impl From<std::num::TryFromIntError> for OneErr {
+    #[track_caller]
+    fn from(e: std::num::TryFromIntError) -> Self {
+        with_kind(std::io::ErrorKind::InvalidInput, e)
+    }
+}
+
+impl From<std::str::Utf8Error> for OneErr {
+    #[track_caller]
+    fn from(e: std::str::Utf8Error) -> Self {
+        let mut err = with_kind(std::io::ErrorKind::InvalidData, e);
+        err.set_field(VALID_UP_TO_FIELD, e.valid_up_to() as u64);
+        if let Some(len) = e.error_len() {
+            err.set_field(ERROR_LEN_FIELD, len as u64);
+        }
+        err
+    }
+}
+
+impl From<std::string::FromUtf8Error> for OneErr {
+    #[track_caller]
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        Self::from(e.utf8_error())
+    }
+}
+
+impl From<std::net::AddrParseError> for OneErr {
+    #[track_caller]
+    fn from(e: std::net::AddrParseError) -> Self {
+        with_kind(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+impl From<std::time::SystemTimeError> for OneErr {
+    #[track_caller]
+    fn from(e: std::time::SystemTimeError) -> Self {
+        let mut err = with_kind(std::io::ErrorKind::InvalidInput, &e);
+        err.set_field(DURATION_SECS_FIELD, e.duration().as_secs_f64());
+        err
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        OneErr::with_message(POISONED_STR, e)
+    }
+}
+
+impl<T> From<std::sync::mpsc::SendError<T>> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::mpsc::SendError<T>) -> Self {
+        with_kind(std::io::ErrorKind::BrokenPipe, e)
+    }
+}
+
+impl<T> From<std::sync::mpsc::TrySendError<T>> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::mpsc::TrySendError<T>) -> Self {
+        use std::sync::mpsc::TrySendError::*;
+        match e {
+            Full(_) => with_kind(std::io::ErrorKind::WouldBlock, e),
+            Disconnected(_) => with_kind(std::io::ErrorKind::BrokenPipe, e),
+        }
+    }
+}
+
+impl From<std::sync::mpsc::RecvError> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::mpsc::RecvError) -> Self {
+        with_kind(std::io::ErrorKind::BrokenPipe, e)
+    }
+}
+
+impl From<std::sync::mpsc::TryRecvError> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::mpsc::TryRecvError) -> Self {
+        use std::sync::mpsc::TryRecvError::*;
+        match e {
+            Empty => with_kind(std::io::ErrorKind::WouldBlock, e),
+            Disconnected => with_kind(std::io::ErrorKind::BrokenPipe, e),
+        }
+    }
+}
+
+impl From<std::sync::mpsc::RecvTimeoutError> for OneErr {
+    #[track_caller]
+    fn from(e: std::sync::mpsc::RecvTimeoutError) -> Self {
+        use std::sync::mpsc::RecvTimeoutError::*;
+        match e {
+            Timeout => with_kind(std::io::ErrorKind::TimedOut, e),
+            Disconnected => with_kind(std::io::ErrorKind::BrokenPipe, e),
+        }
+    }
+}
+
+impl From<std::fmt::Error> for OneErr {
+    #[track_caller]
+    fn from(e: std::fmt::Error) -> Self {
+        with_kind(std::io::ErrorKind::Other, e)
+    }
+}
+
+impl From<std::ffi::NulError> for OneErr {
+    #[track_caller]
+    fn from(e: std::ffi::NulError) -> Self {
+        let mut err = with_kind(std::io::ErrorKind::InvalidInput, &e);
+        err.set_field(NUL_POSITION_FIELD, e.nul_position() as u64);
+        err
+    }
+}
@@ -0,0 +1,125 @@
+//! Checks for the results of raw system calls.
+//!
+//! [check], [check_ptr] and [check_pthread] turn the C conventions for
+//! signalling failure into a `Result` carrying the os errno,
+//! [retry_eintr] retries interrupted calls, and [SyscallExt::syscall]
+//! names the failed call in a [SYSCALL_FIELD].
+
+use crate::*;
+
+/// Field holding the name of the failed system call,
+/// see [SyscallExt::syscall].
+pub const SYSCALL_FIELD: &str = "syscall";
+
+/// Integer return types of system calls that signal failure with -1.
+pub trait SysRet: Copy {
+    /// Returns true if this value is -1.
+    fn is_minus_one(&self) -> bool;
+}
+
+macro_rules! sys_ret {
+    ($($t:ty)*) => {$(
+        impl SysRet for $t {
+            fn is_minus_one(&self) -> bool {
+                *self == -1
+            }
+        }
+    )*};
+}
+
+sys_ret! { i8 i16 i32 i64 isize }
+
+/// Pointer return types of system calls that signal failure with NULL.
+pub trait SysPtr: Copy {
+    /// Returns true if this pointer is null.
+    fn is_null_ptr(&self) -> bool;
+}
+
+impl<T> SysPtr for *mut T {
+    fn is_null_ptr(&self) -> bool {
+        self.is_null()
+    }
+}
+
+impl<T> SysPtr for *const T {
+    fn is_null_ptr(&self) -> bool {
+        self.is_null()
+    }
+}
+
+/// Attach the name of a failed system call to an error result.
+///
+/// ```rust
+/// use one_err::*;
+///
+/// let err = check(-1_i32).syscall("close").unwrap_err();
+/// assert_eq!(Some("close"), err.get_field(SYSCALL_FIELD));
+/// ```
+pub trait SyscallExt {
+    /// Set the [SYSCALL_FIELD] on the error, if this is an error.
+    fn syscall(self, name: &str) -> Self;
+}
+
+impl<T> SyscallExt for Result<T, OneErr> {
+    fn syscall(self, name: &str) -> Self {
+        self.map_err(|mut e| {
+            e.set_field(SYSCALL_FIELD, name);
+            e
+        })
+    }
+}
+
+impl OneErr {
+    /// Create a OneErr from the current value of the os errno,
+    /// see `std::io::Error::last_os_error`.
+    #[track_caller]
+    pub fn last_os_error() -> Self {
+        std::io::Error::last_os_error().into()
+    }
+}
+
+/// Check the result of a system call that returns -1 on failure
+/// and sets errno.
+#[track_caller]
+pub fn check<T: SysRet>(ret: T) -> Result<T, OneErr> {
+    if ret.is_minus_one() {
+        Err(OneErr::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Check the result of a system call that returns NULL on failure
+/// and sets errno.
+#[track_caller]
+pub fn check_ptr<P: SysPtr>(ptr: P) -> Result<P, OneErr> {
+    if ptr.is_null_ptr() {
+        Err(OneErr::last_os_error())
+    } else {
+        Ok(ptr)
+    }
+}
+
+/// Check the result of a pthread-style call that returns zero on success
+/// or a positive errno value on failure.
+#[track_caller]
+pub fn check_pthread(ret: libc::c_int) -> Result<(), OneErr> {
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(ret.into())
+    }
+}
+
+/// Call `f` until it returns something other than an [ErrNo::Intr] error.
+pub fn retry_eintr<T, F>(mut f: F) -> Result<T, OneErr>
+where
+    F: FnMut() -> Result<T, OneErr>,
+{
+    loop {
+        match f() {
+            Err(e) if e.errno() == ErrNo::Intr => continue,
+            r => return r,
+        }
+    }
+}
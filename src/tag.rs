@@ -0,0 +1,75 @@
+// Stable integer tags for well-known `str_kind()`s,
+// used by the compact encodings in place of the kind string.
+
+use crate::io_error::*;
+use crate::*;
+use std::convert::TryFrom;
+
+// io kinds are tagged from 1, append only
+pub(crate) const IO_KIND_TAGS: &[(u64, &str)] = &[
+    (1, NOT_FOUND_STR),
+    (2, PERMISSION_DENIED_STR),
+    (3, CONNECTION_REFUSED_STR),
+    (4, CONNECTION_RESET_STR),
+    (5, CONNECTION_ABORTED_STR),
+    (6, NOT_CONNECTED_STR),
+    (7, ADDR_IN_USE_STR),
+    (8, ADDR_NOT_AVAILABLE_STR),
+    (9, BROKEN_PIPE_STR),
+    (10, ALREADY_EXISTS_STR),
+    (11, WOULD_BLOCK_STR),
+    (12, INVALID_INPUT_STR),
+    (13, INVALID_DATA_STR),
+    (14, TIMED_OUT_STR),
+    (15, WRITE_ZERO_STR),
+    (16, INTERRUPTED_STR),
+    (17, OTHER_STR),
+    (18, UNEXPECTED_EOF_STR),
+    (19, UNSUPPORTED_STR),
+    (20, OUT_OF_MEMORY_STR),
+    (21, ARGUMENT_LIST_TOO_LONG_STR),
+    (22, RESOURCE_BUSY_STR),
+    (23, CROSSES_DEVICES_STR),
+    (24, NOT_A_DIRECTORY_STR),
+    (25, IS_A_DIRECTORY_STR),
+    (26, EXECUTABLE_FILE_BUSY_STR),
+    (27, FILE_TOO_LARGE_STR),
+    (28, STORAGE_FULL_STR),
+    (29, NOT_SEEKABLE_STR),
+    (30, READ_ONLY_FILESYSTEM_STR),
+    (31, TOO_MANY_LINKS_STR),
+    (32, DEADLOCK_STR),
+    (33, INVALID_FILENAME_STR),
+    (34, DIRECTORY_NOT_EMPTY_STR),
+    (35, NETWORK_DOWN_STR),
+    (36, NETWORK_UNREACHABLE_STR),
+    (37, HOST_UNREACHABLE_STR),
+    (38, QUOTA_EXCEEDED_STR),
+    (39, STALE_NETWORK_FILE_HANDLE_STR),
+];
+
+// errnos are tagged ERRNO_TAG_BASE + their linux errno number,
+// which is independent of the platform doing the encoding
+const ERRNO_TAG_BASE: u64 = 256;
+
+/// The tag for a well-known kind string, if it is one.
+pub(crate) fn kind_to_tag(kind: &str) -> Option<u64> {
+    if let Some((tag, _)) = IO_KIND_TAGS.iter().find(|(_, k)| *k == kind) {
+        return Some(*tag);
+    }
+    ErrNo::from(kind)
+        .to_platform_code(Platform::Linux)
+        .map(|code| ERRNO_TAG_BASE + code as u64)
+}
+
+/// The kind string for a tag, if it is a known tag.
+pub(crate) fn tag_to_kind(tag: u64) -> Option<&'static str> {
+    if let Some((_, kind)) = IO_KIND_TAGS.iter().find(|(t, _)| *t == tag) {
+        return Some(kind);
+    }
+    let code = i32::try_from(tag.checked_sub(ERRNO_TAG_BASE)?).ok()?;
+    match ErrNo::from_platform_code(Platform::Linux, code) {
+        ErrNo::Other => None,
+        errno => Some(errno.into()),
+    }
+}
@@ -1,5 +1,34 @@
 use super::*;
 
+// serializes the tests that depend on the process wide location capture
+// switch, those enabling it and those asserting exact error encodings
+static CAPTURE_LOCATION_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn capture_location_lock() -> std::sync::MutexGuard<'static, ()> {
+    CAPTURE_LOCATION_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Enables location capture until dropped, even if the test fails.
+struct CaptureLocation {
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+impl CaptureLocation {
+    fn enable() -> Self {
+        let _lock = capture_location_lock();
+        OneErr::set_capture_location(true);
+        Self { _lock }
+    }
+}
+
+impl Drop for CaptureLocation {
+    fn drop(&mut self) {
+        OneErr::set_capture_location(false);
+    }
+}
+
 #[test]
 fn assert_bounds() {
     fn assert_bounds<T: 'static + Send + Sync + Unpin>(_t: T) {}
@@ -68,6 +97,8 @@ fn disp_ser_deser() {
     e.set_field("test", "test");
     test("ConnectionReset", "ECONNRESET", "ConnectionReset", e);
 
+    // io::Error::new with an explicit Other kind, on purpose
+    #[allow(clippy::io_other_error)]
     let mut e = OneErr::from(std::io::Error::new(
         std::io::ErrorKind::Other,
         "a message from the future",
@@ -96,11 +127,15 @@ fn disp_ser_deser() {
     let e = OneErr::with_message("CustomMsg", "my msg");
     test("Other", "EOTHER", "CustomMsg", e);
 
+    // OneErr::new as a map_err adapter, on purpose
+    #[allow(clippy::unnecessary_map_on_constructor)]
     let e = <Result<(), &'static str>>::Err("foo")
         .map_err(OneErr::new)
         .unwrap_err();
     test("Other", "EOTHER", "foo", e);
 
+    // the whole Display text of a std error becomes the kind
+    #[allow(invalid_from_utf8)]
     let e = std::str::from_utf8(&[0, 159, 146, 150])
         .map_err(OneErr::new)
         .unwrap_err();
@@ -111,3 +146,1390 @@ fn disp_ser_deser() {
         e,
     );
 }
+
+#[test]
+fn jsonrpc_encode_decode() {
+    let _lock = capture_location_lock();
+    use crate::jsonrpc::*;
+
+    let mut e = OneErr::with_message(ErrNo::NoEnt, "no file");
+    e.set_field("path", "/tmp/x");
+    let rpc = JsonRpcError::from(&e);
+    assert_eq!(METHOD_NOT_FOUND, rpc.code);
+    assert_eq!("no file", rpc.message);
+    let enc = rpc.to_string();
+    assert_eq!(
+        r#"{"code":-32601,"message":"no file","data":{"error":"NotFound","message":"no file","path":"/tmp/x"}}"#,
+        &enc,
+    );
+    let dec: JsonRpcError = enc.parse().unwrap();
+    assert_eq!(e, OneErr::from(dec));
+
+    let mut codes = JsonRpcCodes::default();
+    codes.set_code("Busy", -32000).set_default_code(-32099);
+    assert_eq!(-32000, codes.encode(&OneErr::new("Busy")).code);
+    assert_eq!(-32099, codes.encode(&OneErr::new(ErrNo::Fault)).code);
+    assert_eq!(INTERNAL_ERROR, JsonRpcError::from(OneErr::new("Busy")).code);
+
+    let peer: JsonRpcError =
+        r#"{"code":-32602,"message":"bad","data":[1]}"#.parse().unwrap();
+    let e = OneErr::from(peer);
+    assert_eq!(std::io::ErrorKind::InvalidInput, e.io_kind());
+    assert_eq!(Some("bad"), e.get_message());
+    assert_eq!(Some(-32602), e.get_field::<_, i64>(CODE_FIELD));
+    assert_eq!(Some("[1]"), e.get_field::<_, &str>(DATA_FIELD));
+
+    let peer: JsonRpcError =
+        r#"{"code":-32000,"message":"busy"}"#.parse().unwrap();
+    let e = codes.decode(&peer);
+    assert_eq!("Busy", e.str_kind());
+    let e = OneErr::from(peer);
+    assert_eq!("Other", e.str_kind());
+}
+
+#[test]
+fn serde_error_impls() {
+    use crate::serde_error::*;
+    use serde::de::{Deserialize, Error, IntoDeserializer};
+
+    let e = <OneErr as serde::ser::Error>::custom("bad ser");
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some("bad ser"), e.get_message());
+    assert_eq!(Some(CUSTOM), e.get_field(SERDE_ERROR_FIELD));
+
+    let d: serde::de::value::U64Deserializer<OneErr> =
+        3_u64.into_deserializer();
+    let e = String::deserialize(d).unwrap_err();
+    assert_eq!(Some(INVALID_TYPE), e.get_field(SERDE_ERROR_FIELD));
+    assert_eq!(
+        Some("invalid type: integer `3`, expected a string"),
+        e.get_message(),
+    );
+
+    let e = OneErr::invalid_length(2, &"3 items");
+    assert_eq!(Some(2), e.get_field::<_, u64>(LEN_FIELD));
+    assert_eq!(Some("3 items"), e.get_field(EXPECTED_FIELD));
+
+    let e = OneErr::unknown_variant("Purple", &["Red", "Green", "Blue"]);
+    assert_eq!(Some("Purple"), e.get_field(VARIANT_FIELD));
+    assert_eq!(
+        Some(
+            "unknown variant `Purple`, expected one of `Red`, `Green`, `Blue`"
+        ),
+        e.get_message(),
+    );
+
+    let e = OneErr::missing_field("id");
+    assert_eq!(Some(MISSING_FIELD), e.get_field(SERDE_ERROR_FIELD));
+    assert_eq!(Some("id"), e.get_field(FIELD_FIELD));
+
+    // make sure it survives the wire
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(e, dec);
+}
+
+#[test]
+fn std_error_conversions() {
+    use crate::std_error::*;
+    use std::convert::TryFrom;
+    use std::io::ErrorKind;
+
+    let e = OneErr::from("x1".parse::<i32>().unwrap_err());
+    assert_eq!(ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some("invalid digit found in string"), e.get_message());
+    assert_eq!(Some("InvalidDigit"), e.get_field(INT_ERROR_KIND_FIELD));
+
+    let e = OneErr::from("x".parse::<f64>().unwrap_err());
+    assert_eq!(ErrorKind::InvalidData, e.io_kind());
+
+    let e = OneErr::from(u8::try_from(300_i32).unwrap_err());
+    assert_eq!(ErrorKind::InvalidInput, e.io_kind());
+
+    let bad = vec![0, 159, 146, 150];
+    let e = OneErr::from(String::from_utf8(bad).unwrap_err());
+    assert_eq!(ErrorKind::InvalidData, e.io_kind());
+    assert_eq!("InvalidData", e.str_kind());
+    assert_eq!(Some(1), e.get_field::<_, u64>(VALID_UP_TO_FIELD));
+    assert_eq!(Some(1), e.get_field::<_, u64>(ERROR_LEN_FIELD));
+    let bad = vec![b'a', 0xe2, 0x82];
+    let e = OneErr::from(std::str::from_utf8(&bad).unwrap_err());
+    assert_eq!(None, e.get_field::<_, u64>(ERROR_LEN_FIELD));
+
+    let e = OneErr::from("nope".parse::<std::net::IpAddr>().unwrap_err());
+    assert_eq!(ErrorKind::InvalidData, e.io_kind());
+
+    let later =
+        std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+    let e = OneErr::from(
+        std::time::SystemTime::now()
+            .duration_since(later)
+            .unwrap_err(),
+    );
+    assert_eq!(ErrorKind::InvalidInput, e.io_kind());
+    assert!(e.get_field::<_, f64>(DURATION_SECS_FIELD).unwrap() > 4.0);
+
+    let m = std::sync::Arc::new(std::sync::Mutex::new(()));
+    let m2 = m.clone();
+    let _ = std::thread::spawn(move || {
+        let _g = m2.lock().unwrap();
+        panic!("poison");
+    })
+    .join();
+    let e = OneErr::from(m.lock().unwrap_err());
+    assert_eq!(POISONED_STR, e.str_kind());
+
+    let (s, r) = std::sync::mpsc::channel::<()>();
+    assert_eq!(
+        ErrorKind::WouldBlock,
+        OneErr::from(r.try_recv().unwrap_err()).io_kind()
+    );
+    drop(s);
+    assert_eq!(
+        ErrorKind::BrokenPipe,
+        OneErr::from(r.recv().unwrap_err()).io_kind()
+    );
+    assert_eq!(
+        ErrorKind::BrokenPipe,
+        OneErr::from(r.try_recv().unwrap_err()).io_kind()
+    );
+    let (s, r) = std::sync::mpsc::channel::<()>();
+    drop(r);
+    assert_eq!(
+        ErrorKind::BrokenPipe,
+        OneErr::from(s.send(()).unwrap_err()).io_kind()
+    );
+
+    assert_eq!(ErrorKind::Other, OneErr::from(std::fmt::Error).io_kind());
+
+    let e = OneErr::from(std::ffi::CString::new("a\0b").unwrap_err());
+    assert_eq!(ErrorKind::InvalidInput, e.io_kind());
+    assert_eq!(Some(1), e.get_field::<_, u64>(NUL_POSITION_FIELD));
+
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(e, dec);
+}
+
+#[test]
+fn parse_error_conversions() {
+    use crate::parse_error::*;
+
+    let e = "{\"error\":".parse::<OneErr>().unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some(EOF), e.get_field(CATEGORY_FIELD));
+    assert_eq!(Some(1), e.get_field::<_, u64>(LINE_FIELD));
+
+    let e = serde_json::from_str::<OneErr>("{\"error\":").unwrap_err();
+    let e = OneErr::from(e);
+    assert_eq!(std::io::ErrorKind::UnexpectedEof, e.io_kind());
+    assert_eq!(Some(EOF), e.get_field(CATEGORY_FIELD));
+
+    let e = "{\"bob\": 1}".parse::<OneErr>().unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some("required 'error' field"), e.get_message());
+    assert_eq!(None, e.get_field::<_, u64>(LINE_FIELD));
+
+    let e = "{\n\"a\": [1]}".parse::<OneErr>().unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some(DATA), e.get_field(CATEGORY_FIELD));
+    assert_eq!(Some(2), e.get_field::<_, u64>(LINE_FIELD));
+    assert_eq!(Some(6), e.get_field::<_, u64>(COLUMN_FIELD));
+
+    let e = OneErr::from(serde_json::from_str::<i32>("x").unwrap_err());
+    assert_eq!(Some(SYNTAX), e.get_field(CATEGORY_FIELD));
+
+    let e = OneErr::from(
+        serde_json::from_reader::<_, i32>(crate::test::ErrReader).unwrap_err(),
+    );
+    assert_eq!(std::io::ErrorKind::ConnectionReset, e.io_kind());
+    assert_eq!(Some(IO), e.get_field(CATEGORY_FIELD));
+
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(e, dec);
+}
+
+struct ErrReader;
+
+impl std::io::Read for ErrReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::ConnectionReset.into())
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn parse_error_toml() {
+    use crate::parse_error::*;
+
+    let src = "a = 1\nb = é\n";
+    let e = toml::from_str::<toml::Table>(src).unwrap_err();
+    let e = toml_error(e, src);
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some(TOML), e.get_field(FORMAT_FIELD));
+    assert_eq!(Some(10), e.get_field::<_, u64>(OFFSET_FIELD));
+    assert_eq!(Some(2), e.get_field::<_, u64>(LINE_FIELD));
+    assert_eq!(Some(5), e.get_field::<_, u64>(COLUMN_FIELD));
+
+    let e = OneErr::from(toml::from_str::<toml::Table>(src).unwrap_err());
+    assert_eq!(Some(10), e.get_field::<_, u64>(OFFSET_FIELD));
+    assert_eq!(None, e.get_field::<_, u64>(LINE_FIELD));
+    assert_eq!(None, e.get_field::<_, u64>(COLUMN_FIELD));
+}
+
+#[cfg(feature = "serde_yaml")]
+#[test]
+fn parse_error_yaml() {
+    use crate::parse_error::*;
+
+    let e = serde_yaml::from_str::<serde_yaml::Value>("a: 1\n@b").unwrap_err();
+    let e = OneErr::from(e);
+    assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+    assert_eq!(Some(YAML), e.get_field(FORMAT_FIELD));
+    assert_eq!(Some(2), e.get_field::<_, u64>(LINE_FIELD));
+    assert_eq!(Some(1), e.get_field::<_, u64>(COLUMN_FIELD));
+}
+
+#[test]
+fn fs_wrappers() {
+    use crate::fs::*;
+
+    let dir = std::env::temp_dir()
+        .join(format!("one_err_fs_test_{}", std::process::id()));
+    let file = dir.join("a.txt");
+    let missing = dir.join("missing");
+
+    crate::fs::create_dir_all(&dir).unwrap();
+    crate::fs::write(&file, b"hello").unwrap();
+    assert_eq!(b"hello", &crate::fs::read(&file).unwrap()[..]);
+    assert_eq!(1, crate::fs::read_dir(&dir).unwrap().count());
+
+    let e = OneErr::from(crate::fs::read_to_string(&missing).unwrap_err());
+    assert_eq!(ErrNo::NoEnt, e.errno());
+    assert_eq!(std::io::ErrorKind::NotFound, e.io_kind());
+    assert_eq!(Some("read_to_string"), e.get_field(OP_FIELD));
+    assert_eq!(
+        Some(missing.to_string_lossy().as_ref()),
+        e.get_field(PATH_FIELD),
+    );
+
+    let e = OneErr::from(crate::fs::rename(&missing, &file).unwrap_err());
+    assert_eq!(ErrNo::NoEnt, e.errno());
+    assert_eq!(Some("rename"), e.get_field(OP_FIELD));
+    assert_eq!(Some(file.to_string_lossy().as_ref()), e.get_field(TO_FIELD));
+
+    let e = OneErr::from(crate::fs::create_dir(&dir).unwrap_err());
+    assert_eq!(std::io::ErrorKind::AlreadyExists, e.io_kind());
+
+    // io::Result compatible through `?`
+    fn meta(p: &std::path::Path) -> std::io::Result<u64> {
+        Ok(crate::fs::metadata(p)?.len())
+    }
+    let e = OneErr::from(meta(&missing).unwrap_err());
+    assert_eq!(Some("metadata"), e.get_field(OP_FIELD));
+
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(e, dec);
+
+    crate::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn net_wrappers() {
+    use crate::net::*;
+
+    let listener = tcp_bind("127.0.0.1:0").unwrap();
+    let local = listener.local_addr().unwrap();
+
+    let e = OneErr::from(tcp_bind(local).unwrap_err());
+    assert_eq!(ErrNo::AddrInUse, e.errno());
+    assert_eq!(Some("bind"), e.get_field(OP_FIELD));
+    assert_eq!(Some(local.to_string().as_str()), e.get_field(LOCAL_FIELD));
+
+    drop(listener);
+    let e = OneErr::from(tcp_connect(local).unwrap_err());
+    assert_eq!(ErrNo::ConnRefused, e.errno());
+    assert_eq!(Some("connect"), e.get_field(OP_FIELD));
+    assert_eq!(Some(local.to_string().as_str()), e.get_field(PEER_FIELD));
+
+    let sock = udp_bind("127.0.0.1:0").unwrap();
+    sock.set_nonblocking(true).unwrap();
+    let e = OneErr::from(udp_recv_from(&sock, &mut [0; 8]).unwrap_err());
+    assert_eq!(ErrNo::WouldBlock, e.errno());
+    assert_eq!(Some("recv_from"), e.get_field(OP_FIELD));
+    assert_eq!(
+        Some(sock.local_addr().unwrap().to_string().as_str()),
+        e.get_field(LOCAL_FIELD),
+    );
+
+    let e = OneErr::from(tcp_connect("bad address").unwrap_err());
+    assert_eq!(Some("resolve"), e.get_field(OP_FIELD));
+    assert_eq!(None, e.get_field::<_, &str>(ADDR_FIELD));
+
+    let other = tcp_bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let e = OneErr::from(tcp_connect(&[local, other][..]).unwrap_err());
+    assert_eq!(ErrNo::ConnRefused, e.errno());
+    assert_eq!(Some(other.to_string().as_str()), e.get_field(PEER_FIELD));
+    let tried = format!("{},{}", local, other);
+    assert_eq!(Some(tried.as_str()), e.get_field(ADDR_FIELD));
+
+    #[cfg(unix)]
+    {
+        let e = OneErr::from(unix_connect("/no/such/socket").unwrap_err());
+        assert_eq!(ErrNo::NoEnt, e.errno());
+        assert_eq!(Some("/no/such/socket"), e.get_field(PEER_FIELD));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn process_helpers() {
+    use crate::process::*;
+    use std::process::Command;
+
+    let out = output(Command::new("sh").args(["-c", "echo hi"])).unwrap();
+    assert_eq!(b"hi\n", &out.stdout[..]);
+
+    let e = output(&mut Command::new("/no/such/program")).unwrap_err();
+    assert_eq!(ErrNo::NoEnt, e.errno());
+    assert_eq!(Some("/no/such/program"), e.get_field(PROGRAM_FIELD));
+    assert_eq!(Some("[]"), e.get_field(ARGS_FIELD));
+
+    let script = "head -c 5000 /dev/zero | tr '\\0' x >&2; exit 2";
+    let e = output(Command::new("sh").args(["-c", script])).unwrap_err();
+    assert_eq!(PROCESS_FAILED_STR, e.str_kind());
+    assert_eq!(Some(2), e.get_field::<_, i64>(EXIT_CODE_FIELD));
+    assert_eq!(None, e.get_field::<_, i64>(SIGNAL_FIELD));
+    assert_eq!(
+        STDERR_TAIL_MAX,
+        e.get_field::<_, &str>(STDERR_TAIL_FIELD).unwrap().len(),
+    );
+    let args = format!("[\"-c\",{:?}]", script);
+    assert_eq!(Some(args.as_str()), e.get_field(ARGS_FIELD));
+
+    let e = status(Command::new("sh").args(["-c", "kill -9 $$"])).unwrap_err();
+    assert_eq!(PROCESS_FAILED_STR, e.str_kind());
+    assert_eq!(None, e.get_field::<_, i64>(EXIT_CODE_FIELD));
+    assert_eq!(Some(9), e.get_field::<_, i64>(SIGNAL_FIELD));
+    assert_eq!(None, e.get_field::<_, &str>(STDERR_TAIL_FIELD));
+
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(PROCESS_FAILED_STR, dec.str_kind());
+    assert_eq!(Some(9), dec.get_field::<_, u64>(SIGNAL_FIELD));
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn sys_helpers() {
+    let e = check(unsafe { libc::close(-1) })
+        .syscall("close")
+        .unwrap_err();
+    assert_eq!(ErrNo::BadF, e.errno());
+    assert_eq!(Some("close"), e.get_field(SYSCALL_FIELD));
+    assert_eq!(3, check(3_isize).unwrap());
+
+    let e = check_ptr(std::ptr::null_mut::<u8>()).syscall("malloc");
+    assert!(e.is_err());
+    let mut x = 1_u8;
+    assert!(check_ptr(&mut x as *mut u8).is_ok());
+    assert!(check_ptr(std::ptr::null::<u8>()).is_err());
+    assert!(check_ptr(&x as *const u8).is_ok());
+
+    assert!(check_pthread(0).is_ok());
+    let e = check_pthread(libc::EBUSY).syscall("pthread_mutex_trylock");
+    assert_eq!(ErrNo::Busy, e.unwrap_err().errno());
+
+    let mut count = 0;
+    let r = retry_eintr(|| {
+        count += 1;
+        if count < 3 {
+            Err(OneErr::from(ErrNo::Intr))
+        } else {
+            Ok(count)
+        }
+    });
+    assert_eq!(3, r.unwrap());
+    let r: Result<(), OneErr> = retry_eintr(|| Err(ErrNo::WouldBlock.into()));
+    assert_eq!(ErrNo::WouldBlock, r.unwrap_err().errno());
+}
+
+#[test]
+fn eai_err() {
+    let _lock = capture_location_lock();
+    let e = OneErr::from(EaiErr::Again);
+    assert_eq!(std::io::ErrorKind::Other, e.io_kind());
+    assert_eq!(ErrNo::Other, e.errno());
+    assert_eq!(std::io::ErrorKind::TimedOut, e.eai().into());
+    assert_eq!(crate::eai::EAI_AGAIN_STR, e.str_kind());
+    assert_eq!(EaiErr::Again, e.eai());
+    assert!(e.eai().is_transient());
+    assert!(!EaiErr::NoName.is_transient());
+    assert_eq!(r#"{"error":"EAI_AGAIN"}"#, &e.to_string());
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(ErrNo::Other, dec.errno());
+    assert_eq!(e, dec);
+
+    let e = OneErr::with_message("EAI_NONAME", "no such host");
+    assert_eq!(ErrNo::Other, e.errno());
+    assert_eq!(std::io::ErrorKind::NotFound, e.eai().into());
+    assert_eq!(EaiErr::NoName, e.eai());
+    let dec: OneErr = e.to_string().parse().unwrap();
+    assert_eq!(e, dec);
+
+    let e = OneErr::new("EAI_FAIL");
+    assert_eq!(std::io::ErrorKind::Other, e.io_kind());
+    assert_eq!(ErrNo::Other, e.errno());
+    assert_eq!(EaiErr::Other, OneErr::new(ErrNo::NoEnt).eai());
+    assert_eq!(EaiErr::NoName, "EAI_NONAME".into());
+
+    #[cfg(unix)]
+    {
+        assert_eq!(EaiErr::Again, EaiErr::from(libc::EAI_AGAIN));
+        assert_eq!(Some(libc::EAI_NONAME), EaiErr::NoName.code());
+        assert!(check_eai(0).is_ok());
+        let e = check_eai(libc::EAI_SERVICE).unwrap_err();
+        assert_eq!(EaiErr::Service, e.eai());
+        assert_eq!(std::io::ErrorKind::InvalidInput, e.eai().into());
+        assert_eq!(ErrNo::Other, e.errno());
+
+        // unknown codes are kept
+        let e = check_eai(-12345).unwrap_err();
+        assert_eq!(EaiErr::Other, e.eai());
+        assert_eq!(
+            Some(-12345),
+            e.get_field::<_, i64>(crate::eai::EAI_CODE_FIELD),
+        );
+    }
+}
+
+#[test]
+fn errno_description() {
+    assert_eq!("No such file or directory", ErrNo::NoEnt.description());
+    assert_eq!("ENOENT", &format!("{}", ErrNo::NoEnt));
+    assert_eq!(
+        "ENOENT (No such file or directory)",
+        &format!("{:#}", ErrNo::NoEnt),
+    );
+    assert_eq!(
+        "EOTHER (Other / Unrecognized Error)",
+        &format!("{:#}", ErrNo::Other)
+    );
+
+    #[cfg(unix)]
+    {
+        assert!(!ErrNo::NoEnt.strerror().unwrap().is_empty());
+        assert_eq!(None, ErrNo::Other.strerror());
+    }
+}
+
+#[test]
+fn errno_table() {
+    let all: Vec<ErrNo> = ErrNo::all().collect();
+    assert_eq!(74, all.len());
+    assert!(!all.contains(&ErrNo::Other));
+    for e in all {
+        assert_eq!(e, ErrNo::from(i32::from(e)));
+        assert_eq!(e, ErrNo::from(<&'static str>::from(e)));
+        assert_ne!(ErrNo::Other.description(), e.description());
+    }
+    assert_eq!(ErrNo::Perm, ErrNo::from(libc::EACCES));
+    assert_eq!(ErrNo::WouldBlock, ErrNo::from(libc::EWOULDBLOCK));
+    assert_eq!(ErrNo::Other, ErrNo::from(-42));
+    assert_eq!(ErrNo::Other, ErrNo::from("ENOTATHING"));
+
+    assert!(ErrNo::ConnRefused.is_network());
+    assert!(!ErrNo::ConnRefused.is_filesystem());
+    assert!(ErrNo::NoEnt.is_filesystem());
+    assert!(ErrNo::NoSpc.is_filesystem());
+    assert!(ErrNo::NoSpc.is_resource_exhaustion());
+    assert!(ErrNo::MFile.is_resource_exhaustion());
+    assert!(!ErrNo::Inval.is_resource_exhaustion());
+    assert!(ErrNo::Intr.is_transient());
+    assert!(ErrNo::WouldBlock.is_transient());
+    assert!(!ErrNo::NoEnt.is_transient());
+    assert!(!ErrNo::Other.is_network());
+}
+
+#[cfg(unix)]
+#[test]
+fn errno_io_kind_std_parity() {
+    let _lock = capture_location_lock();
+    use std::io::ErrorKind;
+
+    for e in ErrNo::all() {
+        let io_err = std::io::Error::from_raw_os_error(i32::from(e));
+        let std_kind = io_err.kind();
+        let ours = ErrorKind::from(e);
+        match format!("{:?}", std_kind).as_str() {
+            // unstable kinds we cannot name
+            "Uncategorized" | "FilesystemLoop" | "InProgress" => {
+                assert_eq!(ErrorKind::Other, ours, "{}", e)
+            }
+            _ => assert_eq!(std_kind, ours, "{}", e),
+        }
+
+        let one = OneErr::from(io_err);
+        assert_eq!(ours, one.io_kind(), "{}", e);
+        assert_eq!(e, one.errno(), "{}", e);
+
+        let dec: OneErr = one.to_string().parse().unwrap();
+        assert_eq!(one, dec, "{}", e);
+        assert_eq!(ours, dec.io_kind(), "{}", e);
+        assert_eq!(e, dec.errno(), "{}", e);
+
+        // peers may send the errno name rather than the io kind name
+        let dec: OneErr = format!("{{\"error\":\"{}\"}}", e).parse().unwrap();
+        assert_eq!(ours, dec.io_kind(), "{}", e);
+        assert_eq!(e, dec.errno(), "{}", e);
+    }
+
+    // errnos of newer io kinds keep the errno string on the wire
+    let e = OneErr::from(ErrNo::NoSpc);
+    assert_eq!(ErrorKind::StorageFull, e.io_kind());
+    assert_eq!("ENOSPC", e.str_kind());
+    assert_eq!(r#"{"error":"ENOSPC"}"#, &e.to_string());
+    let e = OneErr::from(ErrNo::DQuot);
+    assert_eq!(ErrorKind::QuotaExceeded, e.io_kind());
+    assert_eq!(r#"{"error":"EDQUOT"}"#, &e.to_string());
+    assert_eq!(ErrNo::Stale, ErrNo::from(ErrorKind::StaleNetworkFileHandle));
+    let e = OneErr::from(ErrNo::NoEnt);
+    assert_eq!(r#"{"error":"NotFound"}"#, &e.to_string());
+
+    // EOPNOTSUPP shares the Unsupported kind with ENOSYS
+    let e = OneErr::from(ErrNo::OpNotSupp);
+    assert_eq!(ErrorKind::Unsupported, e.io_kind());
+    assert_eq!(ErrNo::OpNotSupp, e.errno());
+    assert_eq!(r#"{"error":"EOPNOTSUPP"}"#, &e.to_string());
+    assert_eq!(ErrNo::NoSys, OneErr::from(ErrNo::NoSys).errno());
+}
+
+#[test]
+fn errno_platform_tables() {
+    let platforms = [
+        Platform::Linux,
+        Platform::Darwin,
+        Platform::FreeBsd,
+        Platform::OpenBsd,
+        Platform::WindowsCrt,
+    ];
+    for p in platforms.iter().copied() {
+        for e in ErrNo::all() {
+            if let Some(code) = e.to_platform_code(p) {
+                assert_eq!(e, ErrNo::from_platform_code(p, code), "{:?}", p);
+            }
+        }
+        assert_eq!(None, ErrNo::Other.to_platform_code(p));
+        assert_eq!(ErrNo::Other, ErrNo::from_platform_code(p, 0));
+        assert_eq!(ErrNo::Perm, ErrNo::from_platform_code(p, 13));
+    }
+
+    assert_eq!(
+        ErrNo::ConnRefused,
+        ErrNo::from_platform_code(Platform::Darwin, 61)
+    );
+    assert_eq!(
+        ErrNo::DeadLk,
+        ErrNo::from_platform_code(Platform::FreeBsd, 11)
+    );
+    assert_eq!(
+        ErrNo::WouldBlock,
+        ErrNo::from_platform_code(Platform::WindowsCrt, 140)
+    );
+    assert_eq!(None, ErrNo::NoStr.to_platform_code(Platform::FreeBsd));
+    assert_eq!(
+        Some(107),
+        ErrNo::ConnRefused.to_platform_code(Platform::WindowsCrt)
+    );
+
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "arm",
+            target_arch = "aarch64"
+        )
+    ))]
+    for e in ErrNo::all() {
+        assert_eq!(Some(i32::from(e)), e.to_platform_code(Platform::Linux));
+    }
+}
+
+#[test]
+fn winerr_table() {
+    let all: Vec<WinErr> = WinErr::all().collect();
+    assert!(!all.contains(&WinErr::Other));
+    for e in all {
+        let code = e.code().unwrap();
+        assert_eq!(e, WinErr::from(code));
+        assert_eq!(e, WinErr::from(e.hresult().unwrap()));
+        assert_eq!(e, WinErr::from(<&'static str>::from(e)));
+        assert_ne!(WinErr::Other.description(), e.description());
+    }
+
+    assert_eq!(WinErr::FileNotFound, WinErr::from(2));
+    assert_eq!(WinErr::SharingViolation, WinErr::from(32));
+    assert_eq!(ErrNo::NoEnt, WinErr::from(2).errno());
+    assert_eq!(ErrNo::Busy, WinErr::from(32).errno());
+    assert_eq!(ErrNo::ConnReset, WinErr::from(10054).errno());
+    assert_eq!(Some(0x80070005_u32 as i32), WinErr::AccessDenied.hresult());
+    assert_eq!(WinErr::Other, WinErr::from(-42));
+    // E_FAIL is not a FACILITY_WIN32 hresult
+    assert_eq!(WinErr::Other, WinErr::from(0x80004005_u32 as i32));
+    assert_eq!(None, WinErr::Other.code());
+
+    assert_eq!(
+        Some("FACILITY_WIN32"),
+        WinErr::hresult_facility(0x80070005_u32 as i32)
+    );
+    assert_eq!(
+        Some("FACILITY_ITF"),
+        WinErr::hresult_facility(0x80040154_u32 as i32)
+    );
+    assert_eq!(None, WinErr::hresult_facility(5));
+
+    let err = OneErr::from(WinErr::from(32));
+    assert_eq!("ERROR_SHARING_VIOLATION", err.str_kind());
+    assert_eq!(std::io::ErrorKind::Other, err.io_kind());
+    assert_eq!(ErrNo::Other, err.errno());
+    assert_eq!(WinErr::SharingViolation, err.winerr());
+    assert_eq!(std::io::ErrorKind::ResourceBusy, err.winerr().into());
+    let dec: OneErr = err.to_string().parse().unwrap();
+    assert_eq!(err, dec);
+    assert_eq!(ErrNo::Other, dec.errno());
+    assert_eq!(
+        "\"WSAECONNRESET\"",
+        serde_json::to_string(&WinErr::WsaEConnReset).unwrap()
+    );
+}
+
+#[test]
+fn exit_codes() {
+    let _lock = capture_location_lock();
+    use crate::exit::*;
+
+    let test = |code: u8, err: OneErr| {
+        assert_eq!(code, err.exit_code(), "{}", err);
+    };
+    test(EX_NOINPUT, ErrNo::NoEnt.into());
+    test(EX_NOPERM, ErrNo::Perm.into());
+    test(EX_DATAERR, std::io::ErrorKind::InvalidData.into());
+    test(EX_USAGE, std::io::ErrorKind::InvalidInput.into());
+    test(EX_TEMPFAIL, ErrNo::TimedOut.into());
+    test(EX_TEMPFAIL, ErrNo::ConnRefused.into());
+    test(EX_TEMPFAIL, EaiErr::Again.into());
+    test(EX_NOHOST, EaiErr::NoName.into());
+    test(EX_UNAVAILABLE, std::io::ErrorKind::HostUnreachable.into());
+    test(EX_CANTCREAT, ErrNo::Exist.into());
+    test(EX_IOERR, ErrNo::Io.into());
+    test(EX_SOFTWARE, OneErr::new("ExitCodesTestKind"));
+
+    set_exit_code("ExitCodesTestKind", EX_CONFIG);
+    test(EX_CONFIG, OneErr::new("ExitCodesTestKind"));
+    set_exit_code("ExitCodesTestKind", EX_PROTOCOL);
+    test(EX_PROTOCOL, OneErr::new("ExitCodesTestKind"));
+
+    let mut err = OneErr::with_message("NotFound", "no config");
+    err.set_field("path", "config.json")
+        .set_field("attempt", 2_u64);
+    assert_eq!(
+        "NotFound: no config\n  path: config.json\n  attempt: 2",
+        report(&err),
+    );
+    let err = OneErr::from(crate::fs::read("/one_err/no/such").unwrap_err());
+    assert_eq!(
+        "NotFound: No such file or directory\n  op: read\n  path: /one_err/no/such",
+        report(&err),
+    );
+    assert_eq!("MyKind", report(&OneErr::new("MyKind")));
+
+    assert_eq!(std::process::ExitCode::SUCCESS, crate::main(|| Ok(())));
+    assert_eq!(
+        std::process::ExitCode::from(EX_NOINPUT),
+        crate::main(|| Err(ErrNo::NoEnt.into())),
+    );
+}
+
+#[test]
+fn panic_capture() {
+    use crate::panic::*;
+
+    assert_eq!(Ok(42), crate::catch_unwind(|| 42));
+
+    let line = line!() + 1;
+    let err = crate::catch_unwind(|| panic!("boom {}", 42)).unwrap_err();
+    assert_eq!(PANIC_STR, err.str_kind());
+    assert_eq!(Some("boom 42"), err.get_field(PANIC_MESSAGE_FIELD));
+    let loc: &str = err.get_field(LOCATION_FIELD).unwrap();
+    assert!(loc.contains(&format!("test.rs:{}:", line)), "{}", loc);
+
+    let dec: OneErr = err.to_string().parse().unwrap();
+    assert_eq!(PANIC_STR, dec.str_kind());
+    assert_eq!(Some("boom 42"), dec.get_field(PANIC_MESSAGE_FIELD));
+
+    let err = from_payload(&42_u32);
+    assert_eq!(Some("Box<dyn Any>"), err.get_field(PANIC_MESSAGE_FIELD));
+
+    use std::future::Future;
+    struct NoopWake;
+    impl std::task::Wake for NoopWake {
+        fn wake(self: std::sync::Arc<Self>) {}
+    }
+    let waker = std::task::Waker::from(std::sync::Arc::new(NoopWake));
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut fut = crate::catch_unwind_async(async {
+        if true {
+            panic!("async boom");
+        }
+    });
+    let err = match std::pin::Pin::new(&mut fut).poll(&mut cx) {
+        std::task::Poll::Ready(Err(err)) => err,
+        _ => panic!("expected a captured panic"),
+    };
+    assert_eq!(Some("async boom"), err.get_field(PANIC_MESSAGE_FIELD));
+}
+
+#[test]
+fn location_field() {
+    let err: OneErr = r#"{"error":"MyKind","location":"src/main.rs:10:5"}"#
+        .parse()
+        .unwrap();
+    assert_eq!(Some("src/main.rs:10:5"), err.location());
+    let dec: OneErr = err.to_string().parse().unwrap();
+    assert_eq!(err, dec);
+    assert_eq!(Some("src/main.rs:10:5"), dec.location());
+
+    let res = std::panic::catch_unwind(|| {
+        OneErr::new("MyKind").set_field("location", "nope");
+    });
+    assert!(res.is_err());
+}
+
+#[test]
+fn helper_location() {
+    use serde::de::Error;
+
+    let _capture = CaptureLocation::enable();
+    let here = |line: u32| format!("{}:{}:", file!(), line);
+
+    let line = line!() + 1;
+    let err = check(-1_i32).unwrap_err();
+    assert!(err.location().unwrap().starts_with(&here(line)));
+
+    let line = line!() + 1;
+    let err = OneErr::from(crate::fs::read("/does/not/exist").unwrap_err());
+    assert!(err.location().unwrap().starts_with(&here(line)));
+
+    let line = line!() + 1;
+    let err = OneErr::custom("bad");
+    assert!(err.location().unwrap().starts_with(&here(line)));
+
+    let rpc = crate::jsonrpc::JsonRpcError {
+        code: -32000,
+        message: "busy".into(),
+        data: None,
+    };
+    let line = line!() + 1;
+    let err = OneErr::from(&rpc);
+    assert!(err.location().unwrap().starts_with(&here(line)));
+
+    #[cfg(unix)]
+    {
+        let line = line!() + 2;
+        let err =
+            crate::process::status(&mut std::process::Command::new("false"))
+                .unwrap_err();
+        assert!(err.location().unwrap().starts_with(&here(line)));
+    }
+}
+
+#[cfg(any(feature = "rmp-serde", feature = "ciborium"))]
+#[test]
+fn msgpack_cbor_round_trip() {
+    type Codec = (fn(&OneErr) -> Vec<u8>, fn(&[u8]) -> Result<OneErr, OneErr>);
+    let codecs: Vec<Codec> = vec![
+        #[cfg(feature = "rmp-serde")]
+        (OneErr::to_msgpack, OneErr::from_msgpack),
+        #[cfg(feature = "ciborium")]
+        (OneErr::to_cbor, OneErr::from_cbor),
+    ];
+
+    let mut errs: Vec<OneErr> = ErrNo::all().map(OneErr::from).collect();
+    errs.push(ErrNo::Other.into());
+    errs.push(std::io::ErrorKind::StorageFull.into());
+    errs.push(OneErr::new("MyCustomKind"));
+    errs.push(OneErr::new("EAI_AGAIN"));
+    let mut err = OneErr::with_message("NotFound", "no such thing");
+    err.set_field("path", "/tmp")
+        .set_field("neg", -3_i64)
+        .set_field("u", 7_u64)
+        .set_field("f", 1.5_f64)
+        .set_field("b", true);
+    errs.push(err);
+
+    for (enc, dec) in codecs {
+        for err in errs.iter() {
+            let data = enc(err);
+            let res = dec(&data).unwrap();
+            assert_eq!(err, &res);
+            assert_eq!(err.str_kind(), res.str_kind());
+            assert_eq!(err.io_kind(), res.io_kind());
+            assert_eq!(err.errno(), res.errno());
+        }
+
+        // well-known kinds are integer tagged
+        let data = enc(&OneErr::with_message(ErrNo::NoSpc, "disk full"));
+        assert!(!data.windows(6).any(|w| w == b"ENOSPC"));
+        let res = dec(&data).unwrap();
+        assert_eq!("ENOSPC", res.str_kind());
+        assert_eq!(std::io::ErrorKind::StorageFull, res.io_kind());
+        assert_eq!(ErrNo::NoSpc, res.errno());
+        assert_eq!(Some("disk full"), res.get_message());
+        let data = enc(&OneErr::new("MyCustomKind"));
+        assert!(data.windows(12).any(|w| w == b"MyCustomKind"));
+
+        let err = dec(&[0xff, 0x00]).unwrap_err();
+        assert!(err
+            .get_field::<_, &str>(parse_error::FORMAT_FIELD)
+            .is_some());
+    }
+
+    // only the compact encodings accept integer kind tags
+    let err = "{\"error\":28}".parse::<OneErr>().unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn protobuf_golden_bytes() {
+    let _lock = capture_location_lock();
+    let mut err = OneErr::with_message("NotFound", "gone");
+    err.set_field("n", -3_i64).set_field("x", Value::Null);
+    err.priv_as_inner_mut()
+        .set_field(SOURCE.into(), OneErr::from(ErrNo::Perm).to_string());
+
+    // the wire format of proto/one_err.proto must never change
+    #[rustfmt::skip]
+    const GOLDEN: &[u8] = &[
+        // kind: "NotFound"
+        10, 8, b'N', b'o', b't', b'F', b'o', b'u', b'n', b'd',
+        // io_kind: "NotFound"
+        18, 8, b'N', b'o', b't', b'F', b'o', b'u', b'n', b'd',
+        // errno: "ENOENT"
+        26, 6, b'E', b'N', b'O', b'E', b'N', b'T',
+        // message: "gone"
+        34, 4, b'g', b'o', b'n', b'e',
+        // fields["n"]: i64_value -3 (zigzag 5)
+        42, 7, 10, 1, b'n', 18, 2, 16, 5,
+        // fields["x"]: null
+        42, 3, 10, 1, b'x',
+        // source
+        50, 43,
+            // kind: "PermissionDenied"
+            10, 16, b'P', b'e', b'r', b'm', b'i', b's', b's', b'i',
+                b'o', b'n', b'D', b'e', b'n', b'i', b'e', b'd',
+            // io_kind: "PermissionDenied"
+            18, 16, b'P', b'e', b'r', b'm', b'i', b's', b's', b'i',
+                b'o', b'n', b'D', b'e', b'n', b'i', b'e', b'd',
+            // errno: "EPERM"
+            26, 5, b'E', b'P', b'E', b'R', b'M',
+    ];
+
+    assert_eq!(GOLDEN, &err.to_protobuf()[..]);
+    let dec = OneErr::from_protobuf(GOLDEN).unwrap();
+    assert_eq!(err, dec);
+    assert_eq!("EPERM", proto::OneErr::from(&dec).source.unwrap().errno);
+
+    // peer supplied fields may not use reserved names
+    use prost::Message;
+    use proto::field_value::Value as V;
+    use std::convert::TryFrom;
+    for (name, value) in [
+        ("error", V::I64Value(5)),
+        ("os", V::StringValue("x".into())),
+        ("message", V::BoolValue(true)),
+        ("source", V::StringValue("{}".into())),
+        ("location", V::U64Value(1)),
+    ] {
+        let mut msg = proto::OneErr::from(&OneErr::new("NotFound"));
+        msg.fields
+            .insert(name.into(), proto::FieldValue { value: Some(value) });
+        let err = OneErr::from_protobuf(&msg.encode_to_vec()).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+        assert_eq!(
+            Some(parse_error::PROTOBUF),
+            err.get_field(parse_error::FORMAT_FIELD),
+        );
+    }
+
+    // every kind survives the round trip
+    let mut errs: Vec<OneErr> = ErrNo::all().map(OneErr::from).collect();
+    errs.push(std::io::ErrorKind::StorageFull.into());
+    errs.push(OneErr::new("MyCustomKind"));
+    let mut err = OneErr::new("EAI_AGAIN");
+    err.set_field("u", 7_u64)
+        .set_field("f", 1.5_f64)
+        .set_field("b", true)
+        .set_field("s", "str");
+    errs.push(err);
+    for err in errs {
+        let dec = OneErr::from_protobuf(&err.to_protobuf()).unwrap();
+        assert_eq!(err, dec);
+        assert_eq!(err.str_kind(), dec.str_kind());
+        assert_eq!(err.io_kind(), dec.io_kind());
+        assert_eq!(err.errno(), dec.errno());
+    }
+
+    // foreign producers may only set the errno
+    let msg = proto::OneErr {
+        errno: "ENOSPC".into(),
+        ..Default::default()
+    };
+    assert_eq!(ErrNo::NoSpc, OneErr::try_from(msg).unwrap().errno());
+
+    let err = OneErr::from_protobuf(&[0xff]).unwrap_err();
+    assert_eq!(
+        Some(parse_error::PROTOBUF),
+        err.get_field(parse_error::FORMAT_FIELD)
+    );
+}
+
+#[test]
+fn compact_round_trip() {
+    use crate::compact::*;
+
+    let mut errs: Vec<OneErr> = ErrNo::all().map(OneErr::from).collect();
+    errs.push(ErrNo::Other.into());
+    errs.push(std::io::ErrorKind::StorageFull.into());
+    errs.push(OneErr::new("MyCustomKind"));
+    errs.push(OneErr::new("EAI_AGAIN"));
+    let mut err = OneErr::with_message("NotFound", "no such thing");
+    err.set_field("path", "/tmp")
+        .set_field("neg", -3_i64)
+        .set_field("pos", 3_i64)
+        .set_field("min", i64::MIN)
+        .set_field("u", u64::MAX)
+        .set_field("f", 1.5_f64)
+        .set_field("nan", f64::NAN)
+        .set_field("b", true)
+        .set_field("n", Value::Null);
+    errs.push(err);
+
+    for err in errs.iter() {
+        let data = err.to_compact();
+        let dec = OneErr::from_compact(&data).unwrap();
+        assert_eq!(err.to_string(), dec.to_string());
+        assert_eq!(err.str_kind(), dec.str_kind());
+        assert_eq!(err.io_kind(), dec.io_kind());
+        assert_eq!(err.errno(), dec.errno());
+        assert!(data.len() <= err.to_string().len());
+    }
+
+    // a stream interns repeated field names
+    let mut enc = Encoder::new(Vec::new());
+    let mut sizes = Vec::new();
+    for err in errs.iter() {
+        let before = enc.get_ref().len();
+        enc.encode(err).unwrap();
+        sizes.push(enc.get_ref().len() - before);
+    }
+    let last = errs.last().unwrap();
+    enc.encode(last).unwrap();
+    let data = enc.into_inner();
+    let repeat = data.len() - sizes.iter().sum::<usize>();
+    assert!(repeat < sizes.last().unwrap() - "pathnegposminufnanbn".len());
+
+    let mut dec = Decoder::new(std::io::BufReader::new(&data[..]));
+    for err in errs.iter().chain(std::iter::once(last)) {
+        let res = dec.decode().unwrap().unwrap();
+        assert_eq!(err.to_string(), res.to_string());
+    }
+    assert!(dec.decode().unwrap().is_none());
+    assert_eq!(errs.len() + 1, Decoder::new(&data[..]).count());
+
+    // truncated and corrupt input
+    let data = last.to_compact();
+    let err = OneErr::from_compact(&data[..data.len() - 1]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::UnexpectedEof, err.io_kind());
+    assert_eq!(
+        Some(parse_error::COMPACT),
+        err.get_field(parse_error::FORMAT_FIELD)
+    );
+    let err = OneErr::from_compact(&[VERSION, 0xff, 0x7f]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+    let err = OneErr::from_compact(&[2]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+    let mut data = OneErr::new("X").to_compact();
+    data.push(0);
+    assert!(OneErr::from_compact(&data).is_err());
+    let mut iter = Decoder::new(&[VERSION, 0, 0xff][..]);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+
+    // a failed write does not intern the record's names
+    struct FailSecond(usize, Vec<u8>);
+    impl std::io::Write for FailSecond {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += 1;
+            if self.0 == 2 {
+                return Err(OneErr::from(ErrNo::NoSpc).into());
+            }
+            self.1.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut enc = Encoder::new(FailSecond(0, Vec::new()));
+    enc.encode(&OneErr::new("X")).unwrap();
+    assert!(enc.encode(last).is_err());
+    enc.encode(last).unwrap();
+    let data = enc.into_inner().1;
+    let res = Decoder::new(&data[..]).last().unwrap().unwrap();
+    assert_eq!(last.to_string(), res.to_string());
+}
+
+#[test]
+fn ndjson_writer_reader() {
+    use crate::ndjson::*;
+
+    let mut err = OneErr::with_message("NotFound", "no such thing");
+    err.set_field("path", "/tmp/x\ny");
+
+    let mut w = Writer::new(Vec::new());
+    w.set_timestamp(true).set_sync_policy(SyncPolicy::Every(2));
+    w.write(&err).unwrap();
+    w.write(&ErrNo::Perm.into()).unwrap();
+    let data = w.into_inner();
+    assert_eq!(2, data.iter().filter(|b| **b == b'\n').count());
+
+    let mut r = Reader::new(std::io::BufReader::new(&data[..]));
+    let first = r.next().unwrap().unwrap();
+    assert!(first.get_field::<_, u64>(TIMESTAMP_FIELD).unwrap() > 0);
+    assert_eq!(Some("/tmp/x\ny"), first.get_field("path"));
+    assert_eq!(ErrNo::Perm, r.next().unwrap().unwrap().errno());
+    assert!(r.next().is_none());
+    assert_eq!(2, r.report().records);
+    assert!(!r.report().truncated);
+
+    // corrupt lines are skipped, a complete final line needs no newline
+    let data = b"{\"error\":\"A\"}\n\n\xff\xfe\n[1]\n{\"error\":\"B\"}";
+    let mut r = Reader::new(&data[..]);
+    let kinds = r
+        .by_ref()
+        .map(|e| e.unwrap().str_kind().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["A", "B"], kinds);
+    let report = r.report();
+    let lines = report.skipped_errors.iter().map(|s| s.0);
+    assert_eq!(vec![3, 4], lines.collect::<Vec<_>>());
+    assert_eq!(2, report.skipped);
+    assert!(!report.truncated);
+
+    // long lines are skipped unbuffered, only the last errors are kept
+    let mut data = Vec::new();
+    for _ in 0..MAX_SKIPPED_ERRORS + 2 {
+        data.extend_from_slice(b"[]\n");
+    }
+    data.extend_from_slice(&[b' '; 100]);
+    data.extend_from_slice(b"{\"error\":\"A\"}\n{\"error\":\"B\"}\n");
+    let mut r = Reader::new(std::io::BufReader::with_capacity(8, &data[..]));
+    r.set_max_line_len(32);
+    let kinds = r
+        .by_ref()
+        .map(|e| e.unwrap().str_kind().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["B"], kinds);
+    let report = r.report();
+    assert_eq!(MAX_SKIPPED_ERRORS as u64 + 3, report.skipped);
+    assert_eq!(MAX_SKIPPED_ERRORS, report.skipped_errors.len());
+    let (line, skipped) = report.skipped_errors.back().unwrap();
+    assert_eq!(MAX_SKIPPED_ERRORS as u64 + 3, *line);
+    assert_eq!(std::io::ErrorKind::InvalidData, skipped.io_kind());
+
+    // files rotate by size
+    let dir = std::env::temp_dir()
+        .join(format!("one_err_ndjson_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("errors.ndjson");
+    let mut line = err.clone();
+    line.set_field(SEQUENCE_FIELD, 0_u64);
+    let line_len = line.to_string().len() as u64 + 1;
+    let mut w = Writer::open(&path).unwrap();
+    w.set_sequence(true)
+        .set_sync_policy(SyncPolicy::Always)
+        .set_rotation(&path, line_len * 3, 2);
+    for _ in 0..10 {
+        w.write(&err).unwrap();
+    }
+    drop(w);
+    let read = |p: &std::path::Path| {
+        Reader::new(std::io::BufReader::new(std::fs::File::open(p).unwrap()))
+            .map(|e| e.unwrap().get_field::<_, u64>(SEQUENCE_FIELD).unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec![9], read(&path));
+    assert_eq!(vec![6, 7, 8], read(&dir.join("errors.ndjson.1")));
+    assert_eq!(vec![3, 4, 5], read(&dir.join("errors.ndjson.2")));
+    assert!(!dir.join("errors.ndjson.3").exists());
+
+    // reopening appends, continuing the sequence
+    let mut w = Writer::open(&path).unwrap();
+    w.set_sequence(true);
+    w.write(&err).unwrap();
+    drop(w);
+    assert_eq!(vec![9, 10], read(&path));
+    let err = OneErr::from(Writer::open(dir.join("none/x")).unwrap_err());
+    assert_eq!(Some("open"), err.get_field(crate::fs::OP_FIELD));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn parse_lenient_text() {
+    use std::io::ErrorKind;
+
+    let p = OneErr::parse_lenient;
+
+    // the strict json form is unchanged
+    let err = OneErr::with_message("MyKind", "boom");
+    assert_eq!(err, p(&err.to_string()));
+    assert!("ENOENT".parse::<OneErr>().is_err());
+
+    // bare kinds
+    assert_eq!(ErrNo::NoEnt, p("ENOENT").errno());
+    assert_eq!(ErrNo::NoEnt, p("enoent").errno());
+    assert_eq!(ErrNo::NoEnt, p(&libc::ENOENT.to_string()).errno());
+    assert_eq!(
+        ErrorKind::ConnectionRefused,
+        p("connection refused").io_kind()
+    );
+    assert_eq!(
+        ErrorKind::ConnectionRefused,
+        p("ConnectionRefused").io_kind()
+    );
+    assert_eq!(ErrorKind::NotFound, p("not_found").io_kind());
+    assert_eq!(ErrorKind::NotFound, p("entity not found").io_kind());
+    assert_eq!(ErrNo::NoSpc, p("No space left on device").errno());
+    assert_eq!(EaiErr::Again, p("EAI_AGAIN").eai());
+    assert_eq!(None, p("ENOENT").get_message());
+
+    // KIND: message
+    let err = p("ENOENT: no such file");
+    assert_eq!(ErrNo::NoEnt, err.errno());
+    assert_eq!(Some("no such file"), err.get_message());
+    let err = p("Error: PermissionDenied: nope");
+    assert_eq!(ErrorKind::PermissionDenied, err.io_kind());
+    assert_eq!(Some("nope"), err.get_message());
+
+    // std::io::Error display and trailing descriptions
+    let io = std::io::Error::from_raw_os_error(libc::EACCES);
+    let err = p(&io.to_string());
+    assert_eq!(ErrNo::from(libc::EACCES), err.errno());
+    let line = "ls: cannot access 'x': No such file or directory";
+    let err = p(line);
+    assert_eq!(ErrNo::NoEnt, err.errno());
+    assert_eq!(Some(line), err.get_message());
+
+    // logfmt
+    let err = p(r#"error=ENOENT message="no \"such\" file" path=/tmp/x n=-3"#);
+    assert_eq!(ErrNo::NoEnt, err.errno());
+    assert_eq!(Some("no \"such\" file"), err.get_message());
+    assert_eq!(Some("/tmp/x"), err.get_field("path"));
+    assert_eq!(Some(-3), err.get_field::<_, i64>("n"));
+    let err = p("retries=3 ok=true");
+    assert_eq!("Other", err.str_kind());
+    assert_eq!(Some(3), err.get_field::<_, u64>("retries"));
+    assert_eq!(Some(true), err.get_field::<_, bool>("ok"));
+
+    // unknown text is wrapped
+    for s in [
+        "something odd happened",
+        "a = b",
+        "x=1 then it broke",
+        "{oops",
+    ] {
+        let err = p(s);
+        assert_eq!("Other", err.str_kind());
+        assert_eq!(Some(s), err.get_message());
+    }
+    assert_eq!(None, p("  ").get_message());
+}
+
+#[test]
+fn logfmt_round_trip() {
+    let _lock = capture_location_lock();
+    let mut err = OneErr::with_message("NotFound", "no \"such\"\tfile\n");
+    err.set_field("path", "/tmp/x")
+        .set_field("empty", "")
+        .set_field("looks_bool", "true")
+        .set_field("looks_num", "-12")
+        .set_field("looks_null", "null")
+        .set_field("eq", "a=b")
+        .set_field("bs", "C:\\dir")
+        .set_field("ctl", "\u{1}")
+        .set_field("uni", "héllo")
+        .set_field("neg", -3_i64)
+        .set_field("u", u64::MAX)
+        .set_field("f", 1.0_f64)
+        .set_field("tiny", 1e-300_f64)
+        .set_field("b", false)
+        .set_field("n", Value::Null);
+
+    let line = err.to_logfmt();
+    assert!(!line.contains('\n'));
+    assert!(line
+        .starts_with("error=NotFound message=\"no \\\"such\\\"\\tfile\\n\""));
+    assert!(line.contains(" path=/tmp/x "));
+    assert!(line.contains(" empty=\"\" "));
+    assert!(line.contains(" looks_bool=\"true\" "));
+    assert!(line.contains(" f=1.0 "));
+    assert!(line.contains(" ctl=\"\\u0001\" "));
+    assert!(line.ends_with(" b=false n=null"));
+    let dec = OneErr::from_logfmt(&line).unwrap();
+    assert_eq!(err, dec);
+    let json: OneErr = err.to_string().parse().unwrap();
+    assert_eq!(json.to_string(), dec.to_string());
+
+    // kinds round trip, including custom kinds that need quoting
+    let mut errs: Vec<OneErr> = ErrNo::all().map(OneErr::from).collect();
+    errs.push(std::io::ErrorKind::StorageFull.into());
+    errs.push(OneErr::new("EAI_AGAIN"));
+    errs.push(OneErr::new("my custom kind"));
+    errs.push(OneErr::new("42"));
+    for err in errs {
+        let dec = OneErr::from_logfmt(&err.to_logfmt()).unwrap();
+        assert_eq!(err, dec);
+        assert_eq!(err.str_kind(), dec.str_kind());
+    }
+
+    // keys are sanitized
+    let mut err = OneErr::new("X");
+    err.set_field("a b=\"c", 1_u64);
+    assert_eq!("error=X a_b__c=1", err.to_logfmt());
+
+    for (bad, offset) in [
+        ("", 0),
+        ("plain text", 0),
+        ("a=1 b", 4),
+        ("a=\"open", 7),
+        ("a=\"x\"y", 5),
+        ("a=x\"y", 2),
+        ("a=\"\\q\"", 3),
+    ] {
+        let err = OneErr::from_logfmt(bad).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.io_kind());
+        assert_eq!(
+            Some(parse_error::LOGFMT),
+            err.get_field(parse_error::FORMAT_FIELD)
+        );
+        assert_eq!(
+            Some(offset),
+            err.get_field::<_, u64>(parse_error::OFFSET_FIELD),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn deserialize_limits() {
+    let _lock = capture_location_lock();
+    use crate::limits::*;
+    use serde::de::DeserializeSeed;
+
+    let mut err = OneErr::with_message("NotFound", "gone");
+    err.set_field("path", "/tmp/x").set_field("n", 3_u64);
+    let json = err.to_string();
+
+    // the defaults admit ordinary errors
+    assert_eq!(
+        err,
+        OneErr::from_str_with_limits(&json, &Limits::default()).unwrap()
+    );
+    assert_eq!(
+        err,
+        OneErr::from_str_with_limits(&json, &Limits::unlimited()).unwrap()
+    );
+
+    let check = |limits: Limits, limit: &str| {
+        let e = OneErr::from_str_with_limits(&json, &limits).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, e.io_kind());
+        assert_eq!(Some(limit), e.get_field(LIMIT_FIELD));
+        assert!(e.get_message().unwrap().contains(limit));
+    };
+    let limits = Limits::default();
+    check(
+        Limits {
+            max_fields: 3,
+            ..limits
+        },
+        MAX_FIELDS,
+    );
+    check(
+        Limits {
+            max_key_len: 3,
+            ..limits
+        },
+        MAX_KEY_LEN,
+    );
+    check(
+        Limits {
+            max_value_len: 5,
+            ..limits
+        },
+        MAX_VALUE_LEN,
+    );
+    check(
+        Limits {
+            max_total_len: 10,
+            ..limits
+        },
+        MAX_TOTAL_LEN,
+    );
+
+    // at the limit is fine
+    let exact = Limits {
+        max_fields: 4,
+        max_key_len: "message".len(),
+        max_value_len: "NotFound".len(),
+        max_total_len: json.len(),
+    };
+    assert_eq!(err, OneErr::from_str_with_limits(&json, &exact).unwrap());
+
+    // escaped strings are checked too, as are duplicate keys
+    let e = OneErr::from_str_with_limits(
+        r#"{"error":"X","a":"A\u0041A"}"#,
+        &Limits {
+            max_value_len: 2,
+            ..limits
+        },
+    )
+    .unwrap_err();
+    assert_eq!(Some(MAX_VALUE_LEN), e.get_field(LIMIT_FIELD));
+    assert!(OneErr::from_str_with_limits(
+        r#"{"error":"X","a":1,"a":2}"#,
+        &Limits {
+            max_fields: 2,
+            ..limits
+        },
+    )
+    .is_ok());
+
+    // malformed input is still a plain json error
+    let e = OneErr::from_str_with_limits("{", &limits).unwrap_err();
+    assert_eq!(None, e.get_field::<_, &str>(LIMIT_FIELD));
+    assert!(
+        OneErr::from_str_with_limits(&format!("{} x", json), &limits).is_err()
+    );
+
+    // as a seed for any serde format
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert!((&Limits {
+        max_fields: 1,
+        ..limits
+    })
+        .deserialize(&mut de)
+        .is_err());
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(err, (&limits).deserialize(&mut de).unwrap());
+}
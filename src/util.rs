@@ -15,14 +15,14 @@ impl<'ltr, 'ltf> std::io::Write for IoToFmt<'ltr, 'ltf> {
                 self.0.write_str(buf).map_err(|_| {
                     std::io::Error::from(std::io::ErrorKind::Other)
                 })?;
-                buf.as_bytes().len()
+                buf.len()
             }
             Err(_) => {
                 let tmp = String::from_utf8_lossy(buf);
                 self.0.write_str(&tmp).map_err(|_| {
                     std::io::Error::from(std::io::ErrorKind::Other)
                 })?;
-                tmp.as_bytes().len()
+                tmp.len()
             }
         })
     }
@@ -84,14 +84,73 @@ pub(crate) fn err_kind_to_str(kind: std::io::ErrorKind) -> &'static str {
         WriteZero => WRITE_ZERO_STR,
         Interrupted => INTERRUPTED_STR,
         UnexpectedEof => UNEXPECTED_EOF_STR,
-        //#[cfg(version("1.53"))]
-        //Unsupported => UNSUPPORTED_STR,
-        //#[cfg(version("1.54"))]
-        //OutOfMemory => OUT_OF_MEMORY_STR,
+        Unsupported => UNSUPPORTED_STR,
+        OutOfMemory => OUT_OF_MEMORY_STR,
+        ArgumentListTooLong => ARGUMENT_LIST_TOO_LONG_STR,
+        ResourceBusy => RESOURCE_BUSY_STR,
+        CrossesDevices => CROSSES_DEVICES_STR,
+        NotADirectory => NOT_A_DIRECTORY_STR,
+        IsADirectory => IS_A_DIRECTORY_STR,
+        ExecutableFileBusy => EXECUTABLE_FILE_BUSY_STR,
+        FileTooLarge => FILE_TOO_LARGE_STR,
+        StorageFull => STORAGE_FULL_STR,
+        NotSeekable => NOT_SEEKABLE_STR,
+        ReadOnlyFilesystem => READ_ONLY_FILESYSTEM_STR,
+        TooManyLinks => TOO_MANY_LINKS_STR,
+        Deadlock => DEADLOCK_STR,
+        InvalidFilename => INVALID_FILENAME_STR,
+        DirectoryNotEmpty => DIRECTORY_NOT_EMPTY_STR,
+        NetworkDown => NETWORK_DOWN_STR,
+        NetworkUnreachable => NETWORK_UNREACHABLE_STR,
+        HostUnreachable => HOST_UNREACHABLE_STR,
+        QuotaExceeded => QUOTA_EXCEEDED_STR,
+        StaleNetworkFileHandle => STALE_NETWORK_FILE_HANDLE_STR,
         _ => OTHER_STR,
     }
 }
 
+/// Whether an errno must be kept alongside the io kind it maps to.
+/// It must if that io kind does not map back to the same errno, or if it
+/// is one of the io kinds added after the wire format was set: errors
+/// with those kinds keep the errno string on the wire, e.g. "ENOSPC"
+/// rather than "StorageFull", as peers on older versions expect.
+pub(crate) fn keep_errno(
+    kind: std::io::ErrorKind,
+    errno: crate::ErrNo,
+) -> bool {
+    use std::io::ErrorKind::*;
+    if errno == crate::ErrNo::Other {
+        return false;
+    }
+    if crate::ErrNo::from(kind) != errno {
+        return true;
+    }
+    matches!(
+        kind,
+        Unsupported
+            | OutOfMemory
+            | ArgumentListTooLong
+            | ResourceBusy
+            | CrossesDevices
+            | NotADirectory
+            | IsADirectory
+            | ExecutableFileBusy
+            | FileTooLarge
+            | StorageFull
+            | NotSeekable
+            | ReadOnlyFilesystem
+            | TooManyLinks
+            | Deadlock
+            | InvalidFilename
+            | DirectoryNotEmpty
+            | NetworkDown
+            | NetworkUnreachable
+            | HostUnreachable
+            | QuotaExceeded
+            | StaleNetworkFileHandle
+    )
+}
+
 pub(crate) fn parse_err_str(
     s: &str,
 ) -> (std::io::ErrorKind, Option<crate::ErrNo>) {
@@ -114,10 +173,27 @@ pub(crate) fn parse_err_str(
         WRITE_ZERO_STR => WriteZero,
         INTERRUPTED_STR => Interrupted,
         UNEXPECTED_EOF_STR => UnexpectedEof,
-        //#[cfg(version("1.53"))]
-        //UNSUPPORTED_STR => Unsupported,
-        //#[cfg(version("1.54"))]
-        //OUT_OF_MEMORY_STR => OutOfMemory,
+        UNSUPPORTED_STR => Unsupported,
+        OUT_OF_MEMORY_STR => OutOfMemory,
+        ARGUMENT_LIST_TOO_LONG_STR => ArgumentListTooLong,
+        RESOURCE_BUSY_STR => ResourceBusy,
+        CROSSES_DEVICES_STR => CrossesDevices,
+        NOT_A_DIRECTORY_STR => NotADirectory,
+        IS_A_DIRECTORY_STR => IsADirectory,
+        EXECUTABLE_FILE_BUSY_STR => ExecutableFileBusy,
+        FILE_TOO_LARGE_STR => FileTooLarge,
+        STORAGE_FULL_STR => StorageFull,
+        NOT_SEEKABLE_STR => NotSeekable,
+        READ_ONLY_FILESYSTEM_STR => ReadOnlyFilesystem,
+        TOO_MANY_LINKS_STR => TooManyLinks,
+        DEADLOCK_STR => Deadlock,
+        INVALID_FILENAME_STR => InvalidFilename,
+        DIRECTORY_NOT_EMPTY_STR => DirectoryNotEmpty,
+        NETWORK_DOWN_STR => NetworkDown,
+        NETWORK_UNREACHABLE_STR => NetworkUnreachable,
+        HOST_UNREACHABLE_STR => HostUnreachable,
+        QUOTA_EXCEEDED_STR => QuotaExceeded,
+        STALE_NETWORK_FILE_HANDLE_STR => StaleNetworkFileHandle,
         _ => Other,
     };
 
@@ -132,5 +208,12 @@ pub(crate) fn parse_err_str(
         None
     };
 
+    // errno strings carry the io kind of the errno, any other non-io
+    // kind, e.g. an EAI_* or windows error string, keeps the Other io kind
+    let kind = match os {
+        Some(os) => std::io::ErrorKind::from(os),
+        None => kind,
+    };
+
     (kind, os)
 }
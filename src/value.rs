@@ -161,61 +161,79 @@ impl<'de> serde::Deserialize<'de> for Value {
     where
         D: serde::Deserializer<'de>,
     {
-        struct V;
-
-        impl<'de> serde::de::Visitor<'de> for V {
-            type Value = Value;
-
-            fn expecting(
-                &self,
-                formatter: &mut std::fmt::Formatter<'_>,
-            ) -> std::fmt::Result {
-                formatter.write_str("any valid JSON primitive (arrays / objects not yet supported)")
-            }
-
-            fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
-                Ok(Value::Bool(value))
-            }
-
-            fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
-                Ok(Value::I64(value))
-            }
-
-            fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
-                Ok(Value::U64(value))
-            }
-
-            fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
-                Ok(Value::F64(value))
-            }
-
-            fn visit_str<E>(self, value: &str) -> Result<Value, E>
-            where
-                E: serde::de::Error,
-            {
-                self.visit_string(String::from(value))
-            }
-
-            fn visit_string<E>(self, value: String) -> Result<Value, E> {
-                Ok(Value::String(value.into()))
-            }
-
-            fn visit_none<E>(self) -> Result<Value, E> {
-                Ok(Value::Null)
-            }
-
-            fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                serde::Deserialize::deserialize(deserializer)
-            }
-
-            fn visit_unit<E>(self) -> Result<Value, E> {
-                Ok(Value::Null)
-            }
+        deserializer.deserialize_any(ValueVisitor::<NoCheck>(None))
+    }
+}
+
+pub(crate) type NoCheck = fn(usize) -> Result<(), String>;
+
+/// Visits any json primitive as a Value. If given, the check is passed
+/// the byte length of a string value, failing the visit on an error.
+pub(crate) struct ValueVisitor<F>(pub(crate) Option<F>);
+
+impl<'de, F> serde::de::Visitor<'de> for ValueVisitor<F>
+where
+    F: FnOnce(usize) -> Result<(), String>,
+{
+    type Value = Value;
+
+    fn expecting(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        formatter.write_str(
+            "any valid JSON primitive (arrays / objects not yet supported)",
+        )
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::I64(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::U64(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::F64(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if let Some(check) = self.0 {
+            check(value.len()).map_err(E::custom)?;
+        }
+        Ok(Value::String(value.into()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if let Some(check) = self.0 {
+            check(value.len()).map_err(E::custom)?;
         }
+        Ok(Value::String(value.into()))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
 
-        deserializer.deserialize_any(V)
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
     }
 }
@@ -0,0 +1,1165 @@
+use crate::*;
+use std::io::ErrorKind;
+
+/// Common Windows error codes: Win32 `ERROR_*` / `WAIT_*` values
+/// and Winsock `WSA*` values. These are what `raw_os_error()` returns
+/// on windows, possibly wrapped in an HRESULT.
+///
+/// Unlike [ErrNo] this is pure data, the translation works the same
+/// from any host os.
+///
+/// ```rust
+/// use one_err::*;
+///
+/// // E_ACCESSDENIED, i.e. HRESULT_FROM_WIN32(ERROR_ACCESS_DENIED)
+/// let e = WinErr::from(0x80070005_u32 as i32);
+/// assert_eq!(WinErr::AccessDenied, e);
+/// assert_eq!("ERROR_ACCESS_DENIED", e.to_string());
+/// assert_eq!(ErrNo::Perm, e.errno());
+///
+/// let err = OneErr::from(e);
+/// assert_eq!("ERROR_ACCESS_DENIED", err.str_kind());
+/// assert_eq!(std::io::ErrorKind::Other, err.io_kind());
+/// assert_eq!(
+///     std::io::ErrorKind::PermissionDenied,
+///     err.winerr().into(),
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WinErr {
+    /// ERROR_FILE_NOT_FOUND (2): The system cannot find the file specified
+    FileNotFound,
+
+    /// ERROR_PATH_NOT_FOUND (3): The system cannot find the path specified
+    PathNotFound,
+
+    /// ERROR_TOO_MANY_OPEN_FILES (4): The system cannot open the file
+    TooManyOpenFiles,
+
+    /// ERROR_ACCESS_DENIED (5): Access is denied
+    AccessDenied,
+
+    /// ERROR_INVALID_HANDLE (6): The handle is invalid
+    InvalidHandle,
+
+    /// ERROR_NOT_ENOUGH_MEMORY (8): Not enough memory resources are available to process this command
+    NotEnoughMemory,
+
+    /// ERROR_INVALID_DATA (13): The data is invalid
+    InvalidData,
+
+    /// ERROR_OUTOFMEMORY (14): Not enough memory resources are available to complete this operation
+    OutOfMemory,
+
+    /// ERROR_NOT_SAME_DEVICE (17): The system cannot move the file to a different disk drive
+    NotSameDevice,
+
+    /// ERROR_NO_MORE_FILES (18): There are no more files
+    NoMoreFiles,
+
+    /// ERROR_WRITE_PROTECT (19): The media is write protected
+    WriteProtect,
+
+    /// ERROR_SHARING_VIOLATION (32): The process cannot access the file because it is being used by another process
+    SharingViolation,
+
+    /// ERROR_LOCK_VIOLATION (33): The process cannot access the file because another process has locked a portion of the file
+    LockViolation,
+
+    /// ERROR_HANDLE_EOF (38): Reached the end of the file
+    HandleEof,
+
+    /// ERROR_HANDLE_DISK_FULL (39): The disk is full
+    HandleDiskFull,
+
+    /// ERROR_NOT_SUPPORTED (50): The request is not supported
+    NotSupported,
+
+    /// ERROR_FILE_EXISTS (80): The file exists
+    FileExists,
+
+    /// ERROR_INVALID_PARAMETER (87): The parameter is incorrect
+    InvalidParameter,
+
+    /// ERROR_BROKEN_PIPE (109): The pipe has been ended
+    BrokenPipe,
+
+    /// ERROR_DISK_FULL (112): There is not enough space on the disk
+    DiskFull,
+
+    /// ERROR_CALL_NOT_IMPLEMENTED (120): This function is not supported on this system
+    CallNotImplemented,
+
+    /// ERROR_INSUFFICIENT_BUFFER (122): The data area passed to a system call is too small
+    InsufficientBuffer,
+
+    /// ERROR_INVALID_NAME (123): The filename, directory name, or volume label syntax is incorrect
+    InvalidName,
+
+    /// ERROR_DIR_NOT_EMPTY (145): The directory is not empty
+    DirNotEmpty,
+
+    /// ERROR_BUSY (170): The requested resource is in use
+    Busy,
+
+    /// ERROR_ALREADY_EXISTS (183): Cannot create a file when that file already exists
+    AlreadyExists,
+
+    /// ERROR_FILENAME_EXCED_RANGE (206): The filename or extension is too long
+    FilenameExcedRange,
+
+    /// ERROR_PIPE_BUSY (231): All pipe instances are busy
+    PipeBusy,
+
+    /// ERROR_NO_DATA (232): The pipe is being closed
+    NoData,
+
+    /// ERROR_PIPE_NOT_CONNECTED (233): No process is on the other end of the pipe
+    PipeNotConnected,
+
+    /// WAIT_TIMEOUT (258): The wait operation timed out
+    WaitTimeout,
+
+    /// ERROR_DIRECTORY (267): The directory name is invalid
+    Directory,
+
+    /// ERROR_OPERATION_ABORTED (995): The I/O operation has been aborted because of either a thread exit or an application request
+    OperationAborted,
+
+    /// ERROR_IO_PENDING (997): Overlapped I/O operation is in progress
+    IoPending,
+
+    /// ERROR_NOT_FOUND (1168): Element not found
+    NotFound,
+
+    /// ERROR_CONNECTION_REFUSED (1225): The remote computer refused the network connection
+    ConnectionRefused,
+
+    /// ERROR_TIMEOUT (1460): This operation returned because the timeout period expired
+    Timeout,
+
+    /// WSAEINTR (10004): A blocking operation was interrupted
+    WsaEIntr,
+
+    /// WSAEBADF (10009): The file handle supplied is not valid
+    WsaEBadF,
+
+    /// WSAEACCES (10013): An attempt was made to access a socket in a way forbidden by its access permissions
+    WsaEAcces,
+
+    /// WSAEFAULT (10014): The system detected an invalid pointer address
+    WsaEFault,
+
+    /// WSAEINVAL (10022): An invalid argument was supplied
+    WsaEInval,
+
+    /// WSAEMFILE (10024): Too many open sockets
+    WsaEMFile,
+
+    /// WSAEWOULDBLOCK (10035): A non-blocking socket operation could not be completed immediately
+    WsaEWouldBlock,
+
+    /// WSAEINPROGRESS (10036): A blocking operation is currently executing
+    WsaEInProgress,
+
+    /// WSAEALREADY (10037): An operation was attempted on a non-blocking socket that already had an operation in progress
+    WsaEAlready,
+
+    /// WSAENOTSOCK (10038): An operation was attempted on something that is not a socket
+    WsaENotSock,
+
+    /// WSAEMSGSIZE (10040): A message sent on a datagram socket was larger than the internal message buffer
+    WsaEMsgSize,
+
+    /// WSAEADDRINUSE (10048): Only one usage of each socket address is normally permitted
+    WsaEAddrInUse,
+
+    /// WSAEADDRNOTAVAIL (10049): The requested address is not valid in its context
+    WsaEAddrNotAvail,
+
+    /// WSAENETDOWN (10050): A socket operation encountered a dead network
+    WsaENetDown,
+
+    /// WSAENETUNREACH (10051): A socket operation was attempted to an unreachable network
+    WsaENetUnreach,
+
+    /// WSAENETRESET (10052): The connection has been broken due to keep-alive activity detecting a failure
+    WsaENetReset,
+
+    /// WSAECONNABORTED (10053): An established connection was aborted by the software in your host machine
+    WsaEConnAborted,
+
+    /// WSAECONNRESET (10054): An existing connection was forcibly closed by the remote host
+    WsaEConnReset,
+
+    /// WSAENOBUFS (10055): No buffer space was available for a socket operation
+    WsaENoBufS,
+
+    /// WSAEISCONN (10056): A connect request was made on an already connected socket
+    WsaEIsConn,
+
+    /// WSAENOTCONN (10057): The socket is not connected
+    WsaENotConn,
+
+    /// WSAETIMEDOUT (10060): A connection attempt failed because the connected party did not respond
+    WsaETimedOut,
+
+    /// WSAECONNREFUSED (10061): No connection could be made because the target machine actively refused it
+    WsaEConnRefused,
+
+    /// WSAEHOSTUNREACH (10065): A socket operation was attempted to an unreachable host
+    WsaEHostUnreach,
+
+    /// WSAHOST_NOT_FOUND (11001): No such host is known
+    WsaHostNotFound,
+
+    /// WSATRY_AGAIN (11002): This is usually a temporary error during hostname resolution
+    WsaTryAgain,
+
+    /// A windows error code not in this list
+    Other,
+}
+
+impl std::fmt::Display for WinErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(<&'static str>::from(self))
+    }
+}
+
+struct WinErrInfo {
+    winerr: WinErr,
+    code: i32,
+    name: &'static str,
+    description: &'static str,
+    errno: ErrNo,
+    kind: ErrorKind,
+}
+
+// the numeric code, name, description, and unix / io translations
+// of each WinErr.
+const WINERR_TABLE: &[WinErrInfo] = &[
+    WinErrInfo {
+        winerr: WinErr::FileNotFound,
+        code: 2,
+        name: ERROR_FILE_NOT_FOUND_STR,
+        description: "The system cannot find the file specified",
+        errno: ErrNo::NoEnt,
+        kind: ErrorKind::NotFound,
+    },
+    WinErrInfo {
+        winerr: WinErr::PathNotFound,
+        code: 3,
+        name: ERROR_PATH_NOT_FOUND_STR,
+        description: "The system cannot find the path specified",
+        errno: ErrNo::NoEnt,
+        kind: ErrorKind::NotFound,
+    },
+    WinErrInfo {
+        winerr: WinErr::TooManyOpenFiles,
+        code: 4,
+        name: ERROR_TOO_MANY_OPEN_FILES_STR,
+        description: "The system cannot open the file",
+        errno: ErrNo::MFile,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::AccessDenied,
+        code: 5,
+        name: ERROR_ACCESS_DENIED_STR,
+        description: "Access is denied",
+        errno: ErrNo::Perm,
+        kind: ErrorKind::PermissionDenied,
+    },
+    WinErrInfo {
+        winerr: WinErr::InvalidHandle,
+        code: 6,
+        name: ERROR_INVALID_HANDLE_STR,
+        description: "The handle is invalid",
+        errno: ErrNo::BadF,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::NotEnoughMemory,
+        code: 8,
+        name: ERROR_NOT_ENOUGH_MEMORY_STR,
+        description: "Not enough memory resources are available to process this command",
+        errno: ErrNo::NoMem,
+        kind: ErrorKind::OutOfMemory,
+    },
+    WinErrInfo {
+        winerr: WinErr::InvalidData,
+        code: 13,
+        name: ERROR_INVALID_DATA_STR,
+        description: "The data is invalid",
+        errno: ErrNo::Inval,
+        kind: ErrorKind::InvalidData,
+    },
+    WinErrInfo {
+        winerr: WinErr::OutOfMemory,
+        code: 14,
+        name: ERROR_OUTOFMEMORY_STR,
+        description: "Not enough memory resources are available to complete this operation",
+        errno: ErrNo::NoMem,
+        kind: ErrorKind::OutOfMemory,
+    },
+    WinErrInfo {
+        winerr: WinErr::NotSameDevice,
+        code: 17,
+        name: ERROR_NOT_SAME_DEVICE_STR,
+        description: "The system cannot move the file to a different disk drive",
+        errno: ErrNo::XDev,
+        kind: ErrorKind::CrossesDevices,
+    },
+    WinErrInfo {
+        winerr: WinErr::NoMoreFiles,
+        code: 18,
+        name: ERROR_NO_MORE_FILES_STR,
+        description: "There are no more files",
+        errno: ErrNo::Other,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WriteProtect,
+        code: 19,
+        name: ERROR_WRITE_PROTECT_STR,
+        description: "The media is write protected",
+        errno: ErrNo::ROFS,
+        kind: ErrorKind::ReadOnlyFilesystem,
+    },
+    WinErrInfo {
+        winerr: WinErr::SharingViolation,
+        code: 32,
+        name: ERROR_SHARING_VIOLATION_STR,
+        description: "The process cannot access the file because it is being used by another process",
+        errno: ErrNo::Busy,
+        kind: ErrorKind::ResourceBusy,
+    },
+    WinErrInfo {
+        winerr: WinErr::LockViolation,
+        code: 33,
+        name: ERROR_LOCK_VIOLATION_STR,
+        description: "The process cannot access the file because another process has locked a portion of the file",
+        errno: ErrNo::Busy,
+        kind: ErrorKind::ResourceBusy,
+    },
+    WinErrInfo {
+        winerr: WinErr::HandleEof,
+        code: 38,
+        name: ERROR_HANDLE_EOF_STR,
+        description: "Reached the end of the file",
+        errno: ErrNo::Other,
+        kind: ErrorKind::UnexpectedEof,
+    },
+    WinErrInfo {
+        winerr: WinErr::HandleDiskFull,
+        code: 39,
+        name: ERROR_HANDLE_DISK_FULL_STR,
+        description: "The disk is full",
+        errno: ErrNo::NoSpc,
+        kind: ErrorKind::StorageFull,
+    },
+    WinErrInfo {
+        winerr: WinErr::NotSupported,
+        code: 50,
+        name: ERROR_NOT_SUPPORTED_STR,
+        description: "The request is not supported",
+        errno: ErrNo::OpNotSupp,
+        kind: ErrorKind::Unsupported,
+    },
+    WinErrInfo {
+        winerr: WinErr::FileExists,
+        code: 80,
+        name: ERROR_FILE_EXISTS_STR,
+        description: "The file exists",
+        errno: ErrNo::Exist,
+        kind: ErrorKind::AlreadyExists,
+    },
+    WinErrInfo {
+        winerr: WinErr::InvalidParameter,
+        code: 87,
+        name: ERROR_INVALID_PARAMETER_STR,
+        description: "The parameter is incorrect",
+        errno: ErrNo::Inval,
+        kind: ErrorKind::InvalidInput,
+    },
+    WinErrInfo {
+        winerr: WinErr::BrokenPipe,
+        code: 109,
+        name: ERROR_BROKEN_PIPE_STR,
+        description: "The pipe has been ended",
+        errno: ErrNo::Pipe,
+        kind: ErrorKind::BrokenPipe,
+    },
+    WinErrInfo {
+        winerr: WinErr::DiskFull,
+        code: 112,
+        name: ERROR_DISK_FULL_STR,
+        description: "There is not enough space on the disk",
+        errno: ErrNo::NoSpc,
+        kind: ErrorKind::StorageFull,
+    },
+    WinErrInfo {
+        winerr: WinErr::CallNotImplemented,
+        code: 120,
+        name: ERROR_CALL_NOT_IMPLEMENTED_STR,
+        description: "This function is not supported on this system",
+        errno: ErrNo::NoSys,
+        kind: ErrorKind::Unsupported,
+    },
+    WinErrInfo {
+        winerr: WinErr::InsufficientBuffer,
+        code: 122,
+        name: ERROR_INSUFFICIENT_BUFFER_STR,
+        description: "The data area passed to a system call is too small",
+        errno: ErrNo::Range,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::InvalidName,
+        code: 123,
+        name: ERROR_INVALID_NAME_STR,
+        description: "The filename, directory name, or volume label syntax is incorrect",
+        errno: ErrNo::Inval,
+        kind: ErrorKind::InvalidFilename,
+    },
+    WinErrInfo {
+        winerr: WinErr::DirNotEmpty,
+        code: 145,
+        name: ERROR_DIR_NOT_EMPTY_STR,
+        description: "The directory is not empty",
+        errno: ErrNo::NotEmpty,
+        kind: ErrorKind::DirectoryNotEmpty,
+    },
+    WinErrInfo {
+        winerr: WinErr::Busy,
+        code: 170,
+        name: ERROR_BUSY_STR,
+        description: "The requested resource is in use",
+        errno: ErrNo::Busy,
+        kind: ErrorKind::ResourceBusy,
+    },
+    WinErrInfo {
+        winerr: WinErr::AlreadyExists,
+        code: 183,
+        name: ERROR_ALREADY_EXISTS_STR,
+        description: "Cannot create a file when that file already exists",
+        errno: ErrNo::Exist,
+        kind: ErrorKind::AlreadyExists,
+    },
+    WinErrInfo {
+        winerr: WinErr::FilenameExcedRange,
+        code: 206,
+        name: ERROR_FILENAME_EXCED_RANGE_STR,
+        description: "The filename or extension is too long",
+        errno: ErrNo::NameTooLong,
+        kind: ErrorKind::InvalidFilename,
+    },
+    WinErrInfo {
+        winerr: WinErr::PipeBusy,
+        code: 231,
+        name: ERROR_PIPE_BUSY_STR,
+        description: "All pipe instances are busy",
+        errno: ErrNo::Busy,
+        kind: ErrorKind::ResourceBusy,
+    },
+    WinErrInfo {
+        winerr: WinErr::NoData,
+        code: 232,
+        name: ERROR_NO_DATA_STR,
+        description: "The pipe is being closed",
+        errno: ErrNo::Pipe,
+        kind: ErrorKind::BrokenPipe,
+    },
+    WinErrInfo {
+        winerr: WinErr::PipeNotConnected,
+        code: 233,
+        name: ERROR_PIPE_NOT_CONNECTED_STR,
+        description: "No process is on the other end of the pipe",
+        errno: ErrNo::Pipe,
+        kind: ErrorKind::BrokenPipe,
+    },
+    WinErrInfo {
+        winerr: WinErr::WaitTimeout,
+        code: 258,
+        name: WAIT_TIMEOUT_STR,
+        description: "The wait operation timed out",
+        errno: ErrNo::TimedOut,
+        kind: ErrorKind::TimedOut,
+    },
+    WinErrInfo {
+        winerr: WinErr::Directory,
+        code: 267,
+        name: ERROR_DIRECTORY_STR,
+        description: "The directory name is invalid",
+        errno: ErrNo::NotDir,
+        kind: ErrorKind::NotADirectory,
+    },
+    WinErrInfo {
+        winerr: WinErr::OperationAborted,
+        code: 995,
+        name: ERROR_OPERATION_ABORTED_STR,
+        description: "The I/O operation has been aborted because of either a thread exit or an application request",
+        errno: ErrNo::Intr,
+        kind: ErrorKind::Interrupted,
+    },
+    WinErrInfo {
+        winerr: WinErr::IoPending,
+        code: 997,
+        name: ERROR_IO_PENDING_STR,
+        description: "Overlapped I/O operation is in progress",
+        errno: ErrNo::InProgress,
+        kind: ErrorKind::WouldBlock,
+    },
+    WinErrInfo {
+        winerr: WinErr::NotFound,
+        code: 1168,
+        name: ERROR_NOT_FOUND_STR,
+        description: "Element not found",
+        errno: ErrNo::NoEnt,
+        kind: ErrorKind::NotFound,
+    },
+    WinErrInfo {
+        winerr: WinErr::ConnectionRefused,
+        code: 1225,
+        name: ERROR_CONNECTION_REFUSED_STR,
+        description: "The remote computer refused the network connection",
+        errno: ErrNo::ConnRefused,
+        kind: ErrorKind::ConnectionRefused,
+    },
+    WinErrInfo {
+        winerr: WinErr::Timeout,
+        code: 1460,
+        name: ERROR_TIMEOUT_STR,
+        description: "This operation returned because the timeout period expired",
+        errno: ErrNo::TimedOut,
+        kind: ErrorKind::TimedOut,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEIntr,
+        code: 10004,
+        name: WSAEINTR_STR,
+        description: "A blocking operation was interrupted",
+        errno: ErrNo::Intr,
+        kind: ErrorKind::Interrupted,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEBadF,
+        code: 10009,
+        name: WSAEBADF_STR,
+        description: "The file handle supplied is not valid",
+        errno: ErrNo::BadF,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEAcces,
+        code: 10013,
+        name: WSAEACCES_STR,
+        description: "An attempt was made to access a socket in a way forbidden by its access permissions",
+        errno: ErrNo::Perm,
+        kind: ErrorKind::PermissionDenied,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEFault,
+        code: 10014,
+        name: WSAEFAULT_STR,
+        description: "The system detected an invalid pointer address",
+        errno: ErrNo::Fault,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEInval,
+        code: 10022,
+        name: WSAEINVAL_STR,
+        description: "An invalid argument was supplied",
+        errno: ErrNo::Inval,
+        kind: ErrorKind::InvalidInput,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEMFile,
+        code: 10024,
+        name: WSAEMFILE_STR,
+        description: "Too many open sockets",
+        errno: ErrNo::MFile,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEWouldBlock,
+        code: 10035,
+        name: WSAEWOULDBLOCK_STR,
+        description: "A non-blocking socket operation could not be completed immediately",
+        errno: ErrNo::WouldBlock,
+        kind: ErrorKind::WouldBlock,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEInProgress,
+        code: 10036,
+        name: WSAEINPROGRESS_STR,
+        description: "A blocking operation is currently executing",
+        errno: ErrNo::InProgress,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEAlready,
+        code: 10037,
+        name: WSAEALREADY_STR,
+        description: "An operation was attempted on a non-blocking socket that already had an operation in progress",
+        errno: ErrNo::Already,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENotSock,
+        code: 10038,
+        name: WSAENOTSOCK_STR,
+        description: "An operation was attempted on something that is not a socket",
+        errno: ErrNo::NotSock,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEMsgSize,
+        code: 10040,
+        name: WSAEMSGSIZE_STR,
+        description: "A message sent on a datagram socket was larger than the internal message buffer",
+        errno: ErrNo::MsgSize,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEAddrInUse,
+        code: 10048,
+        name: WSAEADDRINUSE_STR,
+        description: "Only one usage of each socket address is normally permitted",
+        errno: ErrNo::AddrInUse,
+        kind: ErrorKind::AddrInUse,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEAddrNotAvail,
+        code: 10049,
+        name: WSAEADDRNOTAVAIL_STR,
+        description: "The requested address is not valid in its context",
+        errno: ErrNo::AddrNotAvail,
+        kind: ErrorKind::AddrNotAvailable,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENetDown,
+        code: 10050,
+        name: WSAENETDOWN_STR,
+        description: "A socket operation encountered a dead network",
+        errno: ErrNo::NetDown,
+        kind: ErrorKind::NetworkDown,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENetUnreach,
+        code: 10051,
+        name: WSAENETUNREACH_STR,
+        description: "A socket operation was attempted to an unreachable network",
+        errno: ErrNo::NetUnreach,
+        kind: ErrorKind::NetworkUnreachable,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENetReset,
+        code: 10052,
+        name: WSAENETRESET_STR,
+        description: "The connection has been broken due to keep-alive activity detecting a failure",
+        errno: ErrNo::NetReset,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEConnAborted,
+        code: 10053,
+        name: WSAECONNABORTED_STR,
+        description: "An established connection was aborted by the software in your host machine",
+        errno: ErrNo::ConnAborted,
+        kind: ErrorKind::ConnectionAborted,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEConnReset,
+        code: 10054,
+        name: WSAECONNRESET_STR,
+        description: "An existing connection was forcibly closed by the remote host",
+        errno: ErrNo::ConnReset,
+        kind: ErrorKind::ConnectionReset,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENoBufS,
+        code: 10055,
+        name: WSAENOBUFS_STR,
+        description: "No buffer space was available for a socket operation",
+        errno: ErrNo::NoBufS,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEIsConn,
+        code: 10056,
+        name: WSAEISCONN_STR,
+        description: "A connect request was made on an already connected socket",
+        errno: ErrNo::IsConn,
+        kind: ErrorKind::Other,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaENotConn,
+        code: 10057,
+        name: WSAENOTCONN_STR,
+        description: "The socket is not connected",
+        errno: ErrNo::NotConn,
+        kind: ErrorKind::NotConnected,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaETimedOut,
+        code: 10060,
+        name: WSAETIMEDOUT_STR,
+        description: "A connection attempt failed because the connected party did not respond",
+        errno: ErrNo::TimedOut,
+        kind: ErrorKind::TimedOut,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEConnRefused,
+        code: 10061,
+        name: WSAECONNREFUSED_STR,
+        description: "No connection could be made because the target machine actively refused it",
+        errno: ErrNo::ConnRefused,
+        kind: ErrorKind::ConnectionRefused,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaEHostUnreach,
+        code: 10065,
+        name: WSAEHOSTUNREACH_STR,
+        description: "A socket operation was attempted to an unreachable host",
+        errno: ErrNo::HostUnreach,
+        kind: ErrorKind::HostUnreachable,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaHostNotFound,
+        code: 11001,
+        name: WSAHOST_NOT_FOUND_STR,
+        description: "No such host is known",
+        errno: ErrNo::Other,
+        kind: ErrorKind::NotFound,
+    },
+    WinErrInfo {
+        winerr: WinErr::WsaTryAgain,
+        code: 11002,
+        name: WSATRY_AGAIN_STR,
+        description: "This is usually a temporary error during hostname resolution",
+        errno: ErrNo::Other,
+        kind: ErrorKind::TimedOut,
+    },
+];
+
+const WIN_OTHER_INFO: WinErrInfo = WinErrInfo {
+    winerr: WinErr::Other,
+    code: -1,
+    name: WIN_OTHER_STR,
+    description: "Other / Unrecognized Error",
+    errno: ErrNo::Other,
+    kind: ErrorKind::Other,
+};
+
+/// The HRESULT facility wrapping plain Win32 error codes.
+pub const FACILITY_WIN32: u16 = 7;
+
+// well-known HRESULT facility codes
+const FACILITY_TABLE: &[(u16, &str)] = &[
+    (0, "FACILITY_NULL"),
+    (1, "FACILITY_RPC"),
+    (2, "FACILITY_DISPATCH"),
+    (3, "FACILITY_STORAGE"),
+    (4, "FACILITY_ITF"),
+    (FACILITY_WIN32, "FACILITY_WIN32"),
+    (8, "FACILITY_WINDOWS"),
+    (9, "FACILITY_SECURITY"),
+    (10, "FACILITY_CONTROL"),
+    (11, "FACILITY_CERT"),
+    (12, "FACILITY_INTERNET"),
+    (13, "FACILITY_MEDIASERVER"),
+    (14, "FACILITY_MSMQ"),
+    (15, "FACILITY_SETUPAPI"),
+    (16, "FACILITY_SCARD"),
+    (17, "FACILITY_COMPLUS"),
+    (18, "FACILITY_AAF"),
+    (19, "FACILITY_URT"),
+    (20, "FACILITY_ACS"),
+    (21, "FACILITY_DPLAY"),
+    (22, "FACILITY_UMI"),
+    (23, "FACILITY_SXS"),
+    (24, "FACILITY_WINDOWS_CE"),
+    (25, "FACILITY_HTTP"),
+    (26, "FACILITY_USERMODE_COMMONLOG"),
+    (31, "FACILITY_USERMODE_FILTER_MANAGER"),
+    (32, "FACILITY_BACKGROUNDCOPY"),
+    (33, "FACILITY_CONFIGURATION"),
+    (34, "FACILITY_STATE_MANAGEMENT"),
+    (35, "FACILITY_METADIRECTORY"),
+    (36, "FACILITY_WINDOWSUPDATE"),
+    (37, "FACILITY_DIRECTORYSERVICE"),
+    (38, "FACILITY_GRAPHICS"),
+    (39, "FACILITY_SHELL"),
+    (40, "FACILITY_TPM_SERVICES"),
+    (41, "FACILITY_TPM_SOFTWARE"),
+    (48, "FACILITY_PLA"),
+    (49, "FACILITY_FVE"),
+    (50, "FACILITY_FWP"),
+    (51, "FACILITY_WINRM"),
+    (52, "FACILITY_NDIS"),
+    (53, "FACILITY_USERMODE_HYPERVISOR"),
+    (54, "FACILITY_CMI"),
+    (55, "FACILITY_USERMODE_VIRTUALIZATION"),
+    (56, "FACILITY_USERMODE_VOLMGR"),
+    (57, "FACILITY_BCD"),
+    (58, "FACILITY_USERMODE_VHD"),
+    (60, "FACILITY_SDIAG"),
+    (61, "FACILITY_WEBSERVICES"),
+    (80, "FACILITY_WINDOWS_DEFENDER"),
+    (81, "FACILITY_OPC"),
+];
+
+impl WinErr {
+    /// Iterate over all known WinErr values (excluding `WinErr::Other`).
+    pub fn all() -> impl Iterator<Item = WinErr> {
+        WINERR_TABLE.iter().map(|i| i.winerr)
+    }
+
+    fn info(&self) -> &'static WinErrInfo {
+        WINERR_TABLE
+            .iter()
+            .find(|i| i.winerr == *self)
+            .unwrap_or(&WIN_OTHER_INFO)
+    }
+
+    /// The Win32 / Winsock error code, None for `WinErr::Other`.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Self::Other => None,
+            _ => Some(self.info().code),
+        }
+    }
+
+    /// The code wrapped as an HRESULT, i.e. `HRESULT_FROM_WIN32`.
+    pub fn hresult(&self) -> Option<i32> {
+        self.code().map(|c| {
+            (0x8000_0000_u32 | ((FACILITY_WIN32 as u32) << 16) | c as u32)
+                as i32
+        })
+    }
+
+    /// A portable, locale-independent description of this error.
+    pub fn description(&self) -> &'static str {
+        self.info().description
+    }
+
+    /// The closest unix errno.
+    pub fn errno(&self) -> ErrNo {
+        self.info().errno
+    }
+
+    /// The name of the facility of a failure HRESULT, if it is one
+    /// of the well-known facilities.
+    /// Returns None for success codes and plain Win32 codes.
+    pub fn hresult_facility(hresult: i32) -> Option<&'static str> {
+        if hresult >= 0 {
+            return None;
+        }
+        let facility = ((hresult as u32 >> 16) & 0x1fff) as u16;
+        FACILITY_TABLE
+            .iter()
+            .find(|(f, _)| *f == facility)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl From<i32> for WinErr {
+    /// Translate a Win32 / Winsock code. FACILITY_WIN32 HRESULTs are
+    /// unwrapped to the code they carry.
+    fn from(code: i32) -> Self {
+        let code = if code < 0
+            && ((code as u32 >> 16) & 0x1fff) as u16 == FACILITY_WIN32
+        {
+            code & 0xffff
+        } else {
+            code
+        };
+        WINERR_TABLE
+            .iter()
+            .find(|i| i.code == code)
+            .map(|i| i.winerr)
+            .unwrap_or(Self::Other)
+    }
+}
+
+impl From<WinErr> for ErrNo {
+    fn from(e: WinErr) -> Self {
+        e.errno()
+    }
+}
+
+impl From<WinErr> for std::io::ErrorKind {
+    fn from(e: WinErr) -> Self {
+        (&e).into()
+    }
+}
+
+impl From<&WinErr> for std::io::ErrorKind {
+    fn from(e: &WinErr) -> Self {
+        e.info().kind
+    }
+}
+
+impl From<WinErr> for OneErr {
+    #[track_caller]
+    fn from(e: WinErr) -> Self {
+        if let WinErr::Other = e {
+            OneErr::from(std::io::ErrorKind::Other)
+        } else {
+            OneErr::new(e)
+        }
+    }
+}
+
+impl OneErr {
+    /// Get the WinErr associated with this instance, if any.
+    /// Windows error kinds are not errnos, their `io_kind()` and `errno()`
+    /// are `Other`, use [WinErr::errno] or `std::io::ErrorKind::from` on
+    /// the result to classify them.
+    pub fn winerr(&self) -> WinErr {
+        WinErr::from(self.str_kind())
+    }
+}
+
+/// Constants for working with WinErr `str_kind()`.
+pub mod winerr {
+    /// The const str 'ERROR_FILE_NOT_FOUND'.
+    pub const ERROR_FILE_NOT_FOUND_STR: &str = "ERROR_FILE_NOT_FOUND";
+
+    /// The const str 'ERROR_PATH_NOT_FOUND'.
+    pub const ERROR_PATH_NOT_FOUND_STR: &str = "ERROR_PATH_NOT_FOUND";
+
+    /// The const str 'ERROR_TOO_MANY_OPEN_FILES'.
+    pub const ERROR_TOO_MANY_OPEN_FILES_STR: &str = "ERROR_TOO_MANY_OPEN_FILES";
+
+    /// The const str 'ERROR_ACCESS_DENIED'.
+    pub const ERROR_ACCESS_DENIED_STR: &str = "ERROR_ACCESS_DENIED";
+
+    /// The const str 'ERROR_INVALID_HANDLE'.
+    pub const ERROR_INVALID_HANDLE_STR: &str = "ERROR_INVALID_HANDLE";
+
+    /// The const str 'ERROR_NOT_ENOUGH_MEMORY'.
+    pub const ERROR_NOT_ENOUGH_MEMORY_STR: &str = "ERROR_NOT_ENOUGH_MEMORY";
+
+    /// The const str 'ERROR_INVALID_DATA'.
+    pub const ERROR_INVALID_DATA_STR: &str = "ERROR_INVALID_DATA";
+
+    /// The const str 'ERROR_OUTOFMEMORY'.
+    pub const ERROR_OUTOFMEMORY_STR: &str = "ERROR_OUTOFMEMORY";
+
+    /// The const str 'ERROR_NOT_SAME_DEVICE'.
+    pub const ERROR_NOT_SAME_DEVICE_STR: &str = "ERROR_NOT_SAME_DEVICE";
+
+    /// The const str 'ERROR_NO_MORE_FILES'.
+    pub const ERROR_NO_MORE_FILES_STR: &str = "ERROR_NO_MORE_FILES";
+
+    /// The const str 'ERROR_WRITE_PROTECT'.
+    pub const ERROR_WRITE_PROTECT_STR: &str = "ERROR_WRITE_PROTECT";
+
+    /// The const str 'ERROR_SHARING_VIOLATION'.
+    pub const ERROR_SHARING_VIOLATION_STR: &str = "ERROR_SHARING_VIOLATION";
+
+    /// The const str 'ERROR_LOCK_VIOLATION'.
+    pub const ERROR_LOCK_VIOLATION_STR: &str = "ERROR_LOCK_VIOLATION";
+
+    /// The const str 'ERROR_HANDLE_EOF'.
+    pub const ERROR_HANDLE_EOF_STR: &str = "ERROR_HANDLE_EOF";
+
+    /// The const str 'ERROR_HANDLE_DISK_FULL'.
+    pub const ERROR_HANDLE_DISK_FULL_STR: &str = "ERROR_HANDLE_DISK_FULL";
+
+    /// The const str 'ERROR_NOT_SUPPORTED'.
+    pub const ERROR_NOT_SUPPORTED_STR: &str = "ERROR_NOT_SUPPORTED";
+
+    /// The const str 'ERROR_FILE_EXISTS'.
+    pub const ERROR_FILE_EXISTS_STR: &str = "ERROR_FILE_EXISTS";
+
+    /// The const str 'ERROR_INVALID_PARAMETER'.
+    pub const ERROR_INVALID_PARAMETER_STR: &str = "ERROR_INVALID_PARAMETER";
+
+    /// The const str 'ERROR_BROKEN_PIPE'.
+    pub const ERROR_BROKEN_PIPE_STR: &str = "ERROR_BROKEN_PIPE";
+
+    /// The const str 'ERROR_DISK_FULL'.
+    pub const ERROR_DISK_FULL_STR: &str = "ERROR_DISK_FULL";
+
+    /// The const str 'ERROR_CALL_NOT_IMPLEMENTED'.
+    pub const ERROR_CALL_NOT_IMPLEMENTED_STR: &str =
+        "ERROR_CALL_NOT_IMPLEMENTED";
+
+    /// The const str 'ERROR_INSUFFICIENT_BUFFER'.
+    pub const ERROR_INSUFFICIENT_BUFFER_STR: &str = "ERROR_INSUFFICIENT_BUFFER";
+
+    /// The const str 'ERROR_INVALID_NAME'.
+    pub const ERROR_INVALID_NAME_STR: &str = "ERROR_INVALID_NAME";
+
+    /// The const str 'ERROR_DIR_NOT_EMPTY'.
+    pub const ERROR_DIR_NOT_EMPTY_STR: &str = "ERROR_DIR_NOT_EMPTY";
+
+    /// The const str 'ERROR_BUSY'.
+    pub const ERROR_BUSY_STR: &str = "ERROR_BUSY";
+
+    /// The const str 'ERROR_ALREADY_EXISTS'.
+    pub const ERROR_ALREADY_EXISTS_STR: &str = "ERROR_ALREADY_EXISTS";
+
+    /// The const str 'ERROR_FILENAME_EXCED_RANGE'.
+    pub const ERROR_FILENAME_EXCED_RANGE_STR: &str =
+        "ERROR_FILENAME_EXCED_RANGE";
+
+    /// The const str 'ERROR_PIPE_BUSY'.
+    pub const ERROR_PIPE_BUSY_STR: &str = "ERROR_PIPE_BUSY";
+
+    /// The const str 'ERROR_NO_DATA'.
+    pub const ERROR_NO_DATA_STR: &str = "ERROR_NO_DATA";
+
+    /// The const str 'ERROR_PIPE_NOT_CONNECTED'.
+    pub const ERROR_PIPE_NOT_CONNECTED_STR: &str = "ERROR_PIPE_NOT_CONNECTED";
+
+    /// The const str 'WAIT_TIMEOUT'.
+    pub const WAIT_TIMEOUT_STR: &str = "WAIT_TIMEOUT";
+
+    /// The const str 'ERROR_DIRECTORY'.
+    pub const ERROR_DIRECTORY_STR: &str = "ERROR_DIRECTORY";
+
+    /// The const str 'ERROR_OPERATION_ABORTED'.
+    pub const ERROR_OPERATION_ABORTED_STR: &str = "ERROR_OPERATION_ABORTED";
+
+    /// The const str 'ERROR_IO_PENDING'.
+    pub const ERROR_IO_PENDING_STR: &str = "ERROR_IO_PENDING";
+
+    /// The const str 'ERROR_NOT_FOUND'.
+    pub const ERROR_NOT_FOUND_STR: &str = "ERROR_NOT_FOUND";
+
+    /// The const str 'ERROR_CONNECTION_REFUSED'.
+    pub const ERROR_CONNECTION_REFUSED_STR: &str = "ERROR_CONNECTION_REFUSED";
+
+    /// The const str 'ERROR_TIMEOUT'.
+    pub const ERROR_TIMEOUT_STR: &str = "ERROR_TIMEOUT";
+
+    /// The const str 'WSAEINTR'.
+    pub const WSAEINTR_STR: &str = "WSAEINTR";
+
+    /// The const str 'WSAEBADF'.
+    pub const WSAEBADF_STR: &str = "WSAEBADF";
+
+    /// The const str 'WSAEACCES'.
+    pub const WSAEACCES_STR: &str = "WSAEACCES";
+
+    /// The const str 'WSAEFAULT'.
+    pub const WSAEFAULT_STR: &str = "WSAEFAULT";
+
+    /// The const str 'WSAEINVAL'.
+    pub const WSAEINVAL_STR: &str = "WSAEINVAL";
+
+    /// The const str 'WSAEMFILE'.
+    pub const WSAEMFILE_STR: &str = "WSAEMFILE";
+
+    /// The const str 'WSAEWOULDBLOCK'.
+    pub const WSAEWOULDBLOCK_STR: &str = "WSAEWOULDBLOCK";
+
+    /// The const str 'WSAEINPROGRESS'.
+    pub const WSAEINPROGRESS_STR: &str = "WSAEINPROGRESS";
+
+    /// The const str 'WSAEALREADY'.
+    pub const WSAEALREADY_STR: &str = "WSAEALREADY";
+
+    /// The const str 'WSAENOTSOCK'.
+    pub const WSAENOTSOCK_STR: &str = "WSAENOTSOCK";
+
+    /// The const str 'WSAEMSGSIZE'.
+    pub const WSAEMSGSIZE_STR: &str = "WSAEMSGSIZE";
+
+    /// The const str 'WSAEADDRINUSE'.
+    pub const WSAEADDRINUSE_STR: &str = "WSAEADDRINUSE";
+
+    /// The const str 'WSAEADDRNOTAVAIL'.
+    pub const WSAEADDRNOTAVAIL_STR: &str = "WSAEADDRNOTAVAIL";
+
+    /// The const str 'WSAENETDOWN'.
+    pub const WSAENETDOWN_STR: &str = "WSAENETDOWN";
+
+    /// The const str 'WSAENETUNREACH'.
+    pub const WSAENETUNREACH_STR: &str = "WSAENETUNREACH";
+
+    /// The const str 'WSAENETRESET'.
+    pub const WSAENETRESET_STR: &str = "WSAENETRESET";
+
+    /// The const str 'WSAECONNABORTED'.
+    pub const WSAECONNABORTED_STR: &str = "WSAECONNABORTED";
+
+    /// The const str 'WSAECONNRESET'.
+    pub const WSAECONNRESET_STR: &str = "WSAECONNRESET";
+
+    /// The const str 'WSAENOBUFS'.
+    pub const WSAENOBUFS_STR: &str = "WSAENOBUFS";
+
+    /// The const str 'WSAEISCONN'.
+    pub const WSAEISCONN_STR: &str = "WSAEISCONN";
+
+    /// The const str 'WSAENOTCONN'.
+    pub const WSAENOTCONN_STR: &str = "WSAENOTCONN";
+
+    /// The const str 'WSAETIMEDOUT'.
+    pub const WSAETIMEDOUT_STR: &str = "WSAETIMEDOUT";
+
+    /// The const str 'WSAECONNREFUSED'.
+    pub const WSAECONNREFUSED_STR: &str = "WSAECONNREFUSED";
+
+    /// The const str 'WSAEHOSTUNREACH'.
+    pub const WSAEHOSTUNREACH_STR: &str = "WSAEHOSTUNREACH";
+
+    /// The const str 'WSAHOST_NOT_FOUND'.
+    pub const WSAHOST_NOT_FOUND_STR: &str = "WSAHOST_NOT_FOUND";
+
+    /// The const str 'WSATRY_AGAIN'.
+    pub const WSATRY_AGAIN_STR: &str = "WSATRY_AGAIN";
+
+    /// The const str 'WIN_OTHER'.
+    pub const WIN_OTHER_STR: &str = "WIN_OTHER";
+}
+use winerr::*;
+
+impl From<WinErr> for &'static str {
+    fn from(e: WinErr) -> &'static str {
+        (&e).into()
+    }
+}
+
+impl From<&WinErr> for &'static str {
+    fn from(e: &WinErr) -> &'static str {
+        e.info().name
+    }
+}
+
+impl From<&str> for WinErr {
+    fn from(s: &str) -> Self {
+        WINERR_TABLE
+            .iter()
+            .find(|i| i.name == s)
+            .map(|i| i.winerr)
+            .unwrap_or(Self::Other)
+    }
+}
+
+impl serde::Serialize for WinErr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let r: &'static str = self.into();
+        serializer.serialize_str(r)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WinErr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let e: &'de str = serde::Deserialize::deserialize(deserializer)?;
+        Ok(e.into())
+    }
+}